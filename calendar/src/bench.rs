@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Display, fs, path::Path, time::Duration};
+use utils::{AocResult, DynSolution, SolutionPart};
+
+/// Phase of a day that is timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::Parse => "parse",
+            Self::Part1 => "1",
+            Self::Part2 => "2",
+        })
+    }
+}
+
+/// Summary of the timings of one phase, in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub std_dev_ns: f64,
+}
+
+impl Stats {
+    /// Summarizes a non-empty set of timings
+    #[must_use]
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut ns: Vec<f64> = samples.iter().map(|s| s.as_secs_f64() * 1e9).collect();
+        ns.sort_by(f64::total_cmp);
+        #[allow(clippy::cast_precision_loss)]
+        let count = ns.len().max(1) as f64;
+        let mean_ns = ns.iter().sum::<f64>() / count;
+        let middle = ns.len() / 2;
+        let median_ns = match ns.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (ns[middle - 1] + ns[middle]) / 2.0,
+            _ => ns[middle],
+        };
+        let variance = ns.iter().map(|t| (t - mean_ns).powi(2)).sum::<f64>() / count;
+        Self {
+            mean_ns,
+            median_ns,
+            std_dev_ns: variance.sqrt(),
+        }
+    }
+
+    #[must_use]
+    pub fn mean(&self) -> Duration {
+        Duration::from_secs_f64(self.mean_ns / 1e9)
+    }
+
+    #[must_use]
+    pub fn median(&self) -> Duration {
+        Duration::from_secs_f64(self.median_ns / 1e9)
+    }
+
+    #[must_use]
+    pub fn std_dev(&self) -> Duration {
+        Duration::from_secs_f64(self.std_dev_ns / 1e9)
+    }
+
+    /// Change of the median against a baseline, in percent
+    #[must_use]
+    pub fn change(&self, baseline: &Self) -> f64 {
+        if baseline.median_ns > 0.0 {
+            (self.median_ns - baseline.median_ns) / baseline.median_ns * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Timings of every phase of a day
+pub type DayStats = BTreeMap<Phase, Stats>;
+
+/// Runs a day `iterations` times, after one warm up run
+///
+/// # Errors
+///
+/// Fails when the solution fails on the input
+pub fn bench(solution: &dyn DynSolution, input: &str, iterations: u32) -> AocResult<DayStats> {
+    solution.run(input, SolutionPart::Both)?;
+    let mut samples: BTreeMap<Phase, Vec<Duration>> = BTreeMap::new();
+    for _ in 0..iterations.max(1) {
+        let answers = solution.run(input, SolutionPart::Both)?;
+        samples.entry(Phase::Parse).or_default().push(answers.parse);
+        for (phase, answer) in [(Phase::Part1, answers.part1), (Phase::Part2, answers.part2)] {
+            if let Some(answer) = answer {
+                samples.entry(phase).or_default().push(answer.elapsed);
+            }
+        }
+    }
+    Ok(samples
+        .into_iter()
+        .map(|(phase, samples)| (phase, Stats::new(&samples)))
+        .collect())
+}
+
+/// Timings saved by an earlier run to compare against
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub days: BTreeMap<u8, DayStats>,
+}
+
+impl Baseline {
+    /// Reads a baseline, which is empty when the file does not exist
+    ///
+    /// # Errors
+    ///
+    /// Fails when the file exists but is not a valid baseline
+    pub fn load(path: &Path) -> AocResult<Self> {
+        if path.exists() {
+            Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Fails when the file cannot be written
+    pub fn save(&self, path: &Path) -> AocResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Recorded timings of a phase
+    #[must_use]
+    pub fn get(&self, day: u8, phase: Phase) -> Option<&Stats> {
+        self.days.get(&day)?.get(&phase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(&ms(&[2, 4, 4, 4, 5, 5, 7, 9]));
+        assert_eq!(stats.mean(), Duration::from_millis(5));
+        assert_eq!(stats.median(), Duration::from_micros(4500));
+        assert_eq!(stats.std_dev(), Duration::from_millis(2));
+
+        let stats = Stats::new(&ms(&[3, 1, 2]));
+        assert_eq!(stats.median(), Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_change() {
+        let baseline = Stats::new(&ms(&[10]));
+        assert!((Stats::new(&ms(&[12])).change(&baseline) - 20.0).abs() < 1e-9);
+        assert!((Stats::new(&ms(&[9])).change(&baseline) + 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bench() {
+        let registry = crate::registry();
+        let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
+        let stats = bench(registry.get(9).unwrap(), input, 3).unwrap();
+        assert_eq!(
+            stats.keys().copied().collect::<Vec<_>>(),
+            [Phase::Parse, Phase::Part1, Phase::Part2]
+        );
+
+        let baseline = Baseline {
+            days: BTreeMap::from([(9, stats)]),
+        };
+        let json = serde_json::to_string(&baseline).unwrap();
+        let loaded: Baseline = serde_json::from_str(&json).unwrap();
+        let (part1, loaded_part1) = (
+            baseline.get(9, Phase::Part1).unwrap(),
+            loaded.get(9, Phase::Part1).unwrap(),
+        );
+        assert!(loaded_part1.change(part1).abs() < 1e-9);
+        assert!(loaded.get(10, Phase::Part1).is_none());
+    }
+}
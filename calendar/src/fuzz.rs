@@ -0,0 +1,181 @@
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+use utils::{random, DynSolution, RoundTrip};
+
+/// Input on which a parser misbehaved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Finding {
+    /// Parsing panicked
+    Panic { input: String, message: String },
+    /// The parsed input did not render back into the same data
+    Changed { input: String, rendered: String },
+}
+
+impl Finding {
+    /// Input that triggered the finding
+    #[must_use]
+    pub fn input(&self) -> &str {
+        match self {
+            Self::Panic { input, .. } | Self::Changed { input, .. } => input,
+        }
+    }
+}
+
+thread_local! {
+    /// Whether this thread is fuzzing, so its panics are findings
+    static FUZZING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Installs a panic hook that keeps quiet on threads that are fuzzing
+///
+/// Other threads, like tests running next to the fuzzer, still go
+/// through the hook that was there before.
+fn silence_fuzzing_panics() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !FUZZING.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Counts of a fuzzing session
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub runs: u64,
+    pub rejected: u64,
+}
+
+/// Feeds mutations of a non-empty corpus to the parser of a day
+///
+/// Every mutant must either be rejected, or parse into data that renders
+/// as puzzle text which parses back the same. Stops at the first mutant
+/// that panics or fails to round trip.
+///
+/// # Errors
+///
+/// Returns the finding along with how many runs it took
+pub fn fuzz(
+    solution: &dyn DynSolution,
+    corpus: &[String],
+    runs: u64,
+    seed: u64,
+) -> Result<Summary, (Summary, Finding)> {
+    let mut rng = random::Rng::new(seed);
+    let mut summary = Summary::default();
+    // Panics are reported as findings, not printed
+    silence_fuzzing_panics();
+    FUZZING.with(|fuzzing| fuzzing.set(true));
+    let mut finding = None;
+    for _ in 0..runs {
+        let original = rng.choose(corpus);
+        let input = random::mutate(&mut rng, original);
+        summary.runs += 1;
+        match panic::catch_unwind(AssertUnwindSafe(|| solution.round_trip(&input))) {
+            Ok(RoundTrip::Rejected) => summary.rejected += 1,
+            Ok(RoundTrip::Kept) => {}
+            Ok(RoundTrip::Changed { rendered }) => {
+                finding = Some(Finding::Changed { input, rendered });
+                break;
+            }
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(ToString::to_string)
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                finding = Some(Finding::Panic { input, message });
+                break;
+            }
+        }
+    }
+    FUZZING.with(|fuzzing| fuzzing.set(false));
+    finding.map_or(Ok(summary), |finding| Err((summary, finding)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::{AocResult, ParseResult, Registry, Solution, ToPuzzleText};
+
+    #[derive(Debug, PartialEq)]
+    struct Numbers(Vec<u32>);
+
+    impl ToPuzzleText for Numbers {
+        fn to_puzzle_text(&self) -> String {
+            let numbers: Vec<String> = self
+                .0
+                .iter()
+                .filter(|&&n| n != 8)
+                .map(u32::to_string)
+                .collect();
+            numbers.join(" ")
+        }
+    }
+
+    /// Day whose parser panics on a 7, and that loses every 8 when rendered
+    struct Faulty;
+
+    impl Solution for Faulty {
+        const DAY: u8 = 25;
+
+        type Input = Numbers;
+        type Output1 = u32;
+        type Output2 = u32;
+
+        fn parse(input: &str) -> ParseResult<'_, Numbers> {
+            use nom::{
+                character::complete::{space1, u32},
+                combinator::map,
+                multi::separated_list1,
+            };
+            let (rest, numbers) = map(separated_list1(space1, u32), Numbers)(input)?;
+            assert!(!numbers.0.contains(&7), "unlucky number");
+            Ok((rest, numbers))
+        }
+
+        fn part1(_: &Numbers) -> AocResult<u32> {
+            Ok(0)
+        }
+
+        fn part2(_: &Numbers) -> AocResult<u32> {
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn test_fuzz() {
+        let registry = crate::registry();
+        let corpus = [String::from(
+            "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45",
+        )];
+        let summary = fuzz(registry.get(9).unwrap(), &corpus, 500, 1).unwrap();
+        assert_eq!(summary.runs, 500);
+        assert!(summary.rejected < summary.runs);
+    }
+
+    #[test]
+    fn test_findings() {
+        let registry = Registry::new().register::<Faulty>();
+        let faulty = registry.get(25).unwrap();
+        let corpus = [String::from("7")];
+        let (_, finding) = fuzz(faulty, &corpus, 1000, 1).unwrap_err();
+        assert!(
+            matches!(&finding, Finding::Panic { message, .. } if message == "unlucky number"),
+            "{finding:?}"
+        );
+
+        let corpus = [String::from("1 8")];
+        let (_, finding) = fuzz(faulty, &corpus, 1000, 1).unwrap_err();
+        assert!(matches!(finding, Finding::Changed { .. }), "{finding:?}");
+        assert!(finding.input().contains('8'));
+        assert!(!FUZZING.with(Cell::get));
+    }
+}
@@ -0,0 +1,82 @@
+pub mod bench;
+pub mod fuzz;
+
+use utils::Registry;
+
+/// Builds the registry of every solved day
+///
+/// New days are added here by `cargo xtask create`.
+#[must_use]
+pub fn registry() -> Registry {
+    Registry::new()
+        .register::<day_01::Day01>()
+        .register::<day_02::Day02>()
+        .register::<day_03::Day03>()
+        .register::<day_04::Day04>()
+        .register::<day_05::Day05>()
+        .register::<day_06::Day06>()
+        .register::<day_07::Day07>()
+        .register::<day_08::Day08>()
+        .register::<day_09::Day09>()
+        .register::<day_10::Day10>()
+        .register::<day_11::Day11>()
+        .register::<day_12::Day12>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::SolutionPart;
+
+    #[test]
+    fn test_registry() {
+        let registry = registry();
+        assert_eq!(registry.len(), 12);
+        assert!(registry.iter().map(|s| s.day()).eq(1..=12));
+    }
+
+    #[test]
+    fn test_run() {
+        let registry = registry();
+        let day = registry.get(9).unwrap();
+        let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
+        let answers = day.run(input, SolutionPart::Both).unwrap();
+        assert_eq!(answers.part1.unwrap().answer, "114");
+        assert_eq!(answers.part2.unwrap().answer, "2");
+
+        let answers = day.run(input, SolutionPart::PartTwo).unwrap();
+        assert_eq!(answers.part1, None);
+        assert_eq!(answers.part2.unwrap().answer, "2");
+    }
+
+    #[test]
+    fn test_run_errors() {
+        let registry = registry();
+        let cases = [
+            (1, "1abc2\nxyz", "No digits in \"xyz\""),
+            (
+                5,
+                "seeds: 1 2\n\nseed-to-dirt map:\n1 2 3",
+                "No location in the almanac",
+            ),
+            (8, "L\n\nAAA = (BBB, BBB)", "No node BBB"),
+            (
+                10,
+                "S-7\n|.|\n|.|",
+                "Loop leaves the grid at row 3, column 1",
+            ),
+            (11, "#.\n.x", "line 2, column 2: expected observation"),
+        ];
+        for (day, input, expected) in cases {
+            let error = registry
+                .get(day)
+                .unwrap()
+                .run(input, SolutionPart::Both)
+                .unwrap_err();
+            assert!(
+                error.to_string().starts_with(expected),
+                "day {day}: {error}"
+            );
+        }
+    }
+}
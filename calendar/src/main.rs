@@ -0,0 +1,257 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use calendar::{
+    bench::{bench, Baseline},
+    fuzz::{fuzz, Finding},
+};
+use clap::Parser;
+use utils::{AocResult, DynSolution, PartAnswer, SolutionPart};
+
+/// Runs the whole calendar in a single process
+#[derive(Debug, Parser)]
+#[command(author, about, long_about = None)]
+enum Cli {
+    /// Run every registered day against its input.txt
+    RunAll {
+        /// Workspace containing the day folders
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+        workspace: PathBuf,
+    },
+
+    /// Time the registered days and compare them with a baseline
+    Bench {
+        /// Workspace containing the day folders
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+        workspace: PathBuf,
+
+        /// Only time this day
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// How many times each day is run
+        #[arg(short, long, default_value_t = 10)]
+        iterations: u32,
+
+        /// Baseline to compare with
+        #[arg(long)]
+        baseline: PathBuf,
+
+        /// Save the timings as the new baseline
+        #[arg(long)]
+        save: bool,
+
+        /// Slowdown of the median, in percent, reported as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+
+    /// Feed mutated inputs to the parser of a day and check they round trip
+    Fuzz {
+        /// Workspace containing the day folders
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+        workspace: PathBuf,
+
+        /// Day to fuzz
+        #[arg(long)]
+        day: u8,
+
+        /// How many mutated inputs to try
+        #[arg(short, long, default_value_t = 10_000)]
+        runs: u64,
+
+        /// Seed of the mutations, to reproduce a session
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Extra inputs to mutate besides the day's input.txt
+        #[arg(long)]
+        corpus: Vec<PathBuf>,
+
+        /// Folder the input of a finding is saved in
+        #[arg(long)]
+        artifacts: PathBuf,
+    },
+}
+
+fn main() -> AocResult<()> {
+    match Cli::parse() {
+        Cli::RunAll { workspace } => run_all(&workspace),
+        Cli::Bench {
+            workspace,
+            day,
+            iterations,
+            baseline,
+            save,
+            threshold,
+        } => run_bench(&workspace, day, iterations, &baseline, save, threshold)?,
+        Cli::Fuzz {
+            workspace,
+            day,
+            runs,
+            seed,
+            mut corpus,
+            artifacts,
+        } => {
+            corpus.push(workspace.join(format!("day-{day:0>2}")).join("input.txt"));
+            run_fuzz(day, runs, seed, &corpus, &artifacts)?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs every day and prints a table of the answers and timings
+///
+/// A day that fails is reported in the table without stopping the rest.
+fn run_all(workspace: &Path) {
+    let registry = calendar::registry();
+    let start = Instant::now();
+    println!(
+        "{:>3}  {:>5}  {:<20}  {:>12}",
+        "Day", "Part", "Answer", "Time"
+    );
+    for solution in registry.iter() {
+        run_day(solution, workspace);
+    }
+    println!("Total time: {:.2?}", start.elapsed());
+}
+
+fn run_day(solution: &dyn DynSolution, workspace: &Path) {
+    let day = solution.day();
+    let path = workspace.join(format!("day-{day:0>2}")).join("input.txt");
+    let Ok(input) = read_to_string(&path) else {
+        print_row(day, "-", "missing input.txt", None);
+        return;
+    };
+    match solution.run(&input, SolutionPart::Both) {
+        Ok(answers) => {
+            print_row(day, "parse", "", Some(answers.parse));
+            for (part, answer) in [("1", answers.part1), ("2", answers.part2)] {
+                if let Some(PartAnswer { answer, elapsed }) = answer {
+                    print_row(day, part, &answer, Some(elapsed));
+                }
+            }
+        }
+        Err(err) => print_row(day, "-", &format!("error: {err:#}"), None),
+    }
+}
+
+fn print_row(day: u8, part: &str, answer: &str, elapsed: Option<Duration>) {
+    let elapsed = elapsed.map(|e| format!("{e:.2?}")).unwrap_or_default();
+    println!("{day:>3}  {part:>5}  {answer:<20}  {elapsed:>12}");
+}
+
+/// Times every day, or a single one, and prints their statistics
+///
+/// Phases whose median got slower than the baseline by more than
+/// `threshold` percent are flagged, and make the run fail.
+fn run_bench(
+    workspace: &Path,
+    day: Option<u8>,
+    iterations: u32,
+    baseline_path: &Path,
+    save: bool,
+    threshold: f64,
+) -> AocResult<()> {
+    let registry = calendar::registry();
+    let mut baseline = Baseline::load(baseline_path)?;
+    let mut regressions = 0;
+    println!(
+        "{:>3}  {:>5}  {:>12}  {:>12}  {:>12}  {:>8}",
+        "Day", "Part", "Mean", "Median", "Std dev", "Change"
+    );
+    for solution in registry
+        .iter()
+        .filter(|s| day.is_none_or(|day| s.day() == day))
+    {
+        let day = solution.day();
+        let path = workspace.join(format!("day-{day:0>2}")).join("input.txt");
+        let Ok(input) = read_to_string(&path) else {
+            println!("{day:>3}  {:>5}  missing input.txt", "-");
+            continue;
+        };
+        let stats = match bench(solution, &input, iterations) {
+            Ok(stats) => stats,
+            Err(err) => {
+                println!("{day:>3}  {:>5}  error: {err:#}", "-");
+                continue;
+            }
+        };
+        for (phase, stats) in &stats {
+            let change = baseline.get(day, *phase).map(|old| stats.change(old));
+            let regressed = change.is_some_and(|change| change > threshold);
+            regressions += usize::from(regressed);
+            println!(
+                "{day:>3}  {phase:>5}  {:>12}  {:>12}  {:>12}  {:>8}{}",
+                format!("{:.2?}", stats.mean()),
+                format!("{:.2?}", stats.median()),
+                format!("{:.2?}", stats.std_dev()),
+                change.map(|c| format!("{c:+.1}%")).unwrap_or_default(),
+                if regressed { "  regressed" } else { "" }
+            );
+        }
+        if save {
+            baseline.days.insert(day, stats);
+        }
+    }
+    if save {
+        baseline.save(baseline_path)?;
+        println!("Saved baseline to {}", baseline_path.display());
+    }
+    if regressions > 0 {
+        return Err(anyhow::anyhow!(
+            "{regressions} timings regressed by more than {threshold}%"
+        ));
+    }
+    Ok(())
+}
+
+/// Fuzzes the parser of a day and saves the input of a finding
+///
+/// Files of the corpus that do not exist are skipped, but at least one
+/// must be readable.
+fn run_fuzz(day: u8, runs: u64, seed: u64, corpus: &[PathBuf], artifacts: &Path) -> AocResult<()> {
+    let registry = calendar::registry();
+    let solution = registry
+        .get(day)
+        .ok_or_else(|| anyhow::anyhow!("Day {day} is not in the calendar"))?;
+    let corpus: Vec<String> = corpus
+        .iter()
+        .filter_map(|path| read_to_string(path).ok())
+        .collect();
+    if corpus.is_empty() {
+        return Err(anyhow::anyhow!("No input to mutate for day {day}"));
+    }
+    match fuzz(solution, &corpus, runs, seed) {
+        Ok(summary) => {
+            println!(
+                "Day {day}: {} runs, {} rejected, no findings",
+                summary.runs, summary.rejected
+            );
+            Ok(())
+        }
+        Err((summary, finding)) => {
+            let path = artifacts.join(format!("day-{day:0>2}-seed-{seed}.txt"));
+            std::fs::create_dir_all(artifacts)?;
+            std::fs::write(&path, finding.input())?;
+            match &finding {
+                Finding::Panic { message, .. } => {
+                    println!(
+                        "Day {day}: parser panicked after {} runs: {message}",
+                        summary.runs
+                    );
+                }
+                Finding::Changed { rendered, .. } => {
+                    println!(
+                        "Day {day}: input changed after {} runs, rendered as:\n{rendered}",
+                        summary.runs
+                    );
+                }
+            }
+            Err(anyhow::anyhow!("Input saved to {}", path.display()))
+        }
+    }
+}
@@ -0,0 +1,207 @@
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+struct ShrinkStart<'a> {
+    s: &'a str,
+    i: usize,
+}
+
+impl<'a> ShrinkStart<'a> {
+    const fn new(s: &'a str) -> ShrinkStart<'a> {
+        ShrinkStart { s, i: 0 }
+    }
+}
+
+impl<'a> Iterator for ShrinkStart<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sub_str = self.s.get(self.i..)?;
+        self.i += sub_str.chars().next().map_or(1, char::len_utf8);
+        Some(sub_str)
+    }
+}
+
+struct ExpandEnd<'a> {
+    s: &'a str,
+    i: usize,
+}
+
+impl<'a> ExpandEnd<'a> {
+    const fn new(s: &'a str) -> ExpandEnd<'a> {
+        ExpandEnd { s, i: s.len() }
+    }
+}
+
+impl<'a> Iterator for ExpandEnd<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sub_str = self.s.get(self.i..)?;
+        self.i = self.s[..self.i]
+            .char_indices()
+            .next_back()
+            .map_or(usize::MAX, |(i, _)| i);
+        Some(sub_str)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    lines: Vec<String>,
+}
+
+impl ToPuzzleText for InputData {
+    fn to_puzzle_text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    Ok((
+        "",
+        InputData {
+            lines: input
+                .split('\n')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+        },
+    ))
+}
+
+fn get_number(s: &str) -> Option<u32> {
+    #[allow(clippy::option_if_let_else)]
+    if let Some(i) = s.chars().next().and_then(|c| c.to_digit(10)) {
+        Some(i)
+    } else if s.starts_with("one") {
+        Some(1)
+    } else if s.starts_with("two") {
+        Some(2)
+    } else if s.starts_with("three") {
+        Some(3)
+    } else if s.starts_with("four") {
+        Some(4)
+    } else if s.starts_with("five") {
+        Some(5)
+    } else if s.starts_with("six") {
+        Some(6)
+    } else if s.starts_with("seven") {
+        Some(7)
+    } else if s.starts_with("eight") {
+        Some(8)
+    } else if s.starts_with("nine") {
+        Some(9)
+    } else {
+        None
+    }
+}
+
+fn part1(input: &InputData) -> AocResult<u32> {
+    let mut total = 0_u32;
+    for line in &input.lines {
+        let first = line
+            .chars()
+            .find_map(|c| c.to_digit(10))
+            .ok_or_else(|| anyhow!("No digits in {line:?}"))?;
+        let last = line
+            .chars()
+            .rev()
+            .find_map(|c| c.to_digit(10))
+            .ok_or_else(|| anyhow!("No digits in {line:?}"))?;
+        total += (first * 10) + last;
+    }
+    Ok(total)
+}
+
+fn part2(input: &InputData) -> AocResult<u32> {
+    let mut total = 0_u32;
+    for line in &input.lines {
+        let first = ShrinkStart::new(line)
+            .find_map(get_number)
+            .ok_or_else(|| anyhow!("No number in {line:?}"))?;
+        let last = ExpandEnd::new(line)
+            .find_map(get_number)
+            .ok_or_else(|| anyhow!("No number in {line:?}"))?;
+        total += (first * 10) + last;
+    }
+    Ok(total)
+}
+
+const INPUT_1: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+const INPUT_2: &str = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen";
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const EXAMPLES: &'static [&'static str] = &[INPUT_1, INPUT_2];
+
+    type Input = InputData;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<u32> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<u32> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT_1,
+            InputData {
+                lines: vec![
+                    String::from("1abc2"),
+                    String::from("pqr3stu8vwx"),
+                    String::from("a1b2c3d4e5f"),
+                    String::from("treb7uchet")
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip!(parse, INPUT_1);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT_1, 142);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT_2, 281);
+    }
+
+    #[test]
+    fn test_no_digits() {
+        let input = finish("1abc2\nxyz\n", parse("1abc2\nxyz\n")).unwrap();
+        assert_eq!(
+            part1(&input).unwrap_err().to_string(),
+            "No digits in \"xyz\""
+        );
+        assert_eq!(
+            part2(&input).unwrap_err().to_string(),
+            "No number in \"xyz\""
+        );
+        let input = finish("é1ö", parse("é1ö")).unwrap();
+        assert_eq!(part2(&input).unwrap(), 11);
+    }
+}
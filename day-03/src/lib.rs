@@ -0,0 +1,252 @@
+use itertools::Itertools;
+use utils::grid::Grid;
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Position {
+    row: usize,
+    column_start: usize,
+    column_end: usize,
+}
+
+impl Position {
+    fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row == self.row && (self.column_start..=self.column_end).contains(&col)
+    }
+}
+
+struct PartNumberIterator<'a> {
+    part_number: Option<(Position, Option<u64>)>,
+    indexed_iter: Box<dyn Iterator<Item = ((usize, usize), &'a u8)> + 'a>,
+}
+
+impl<'a> PartNumberIterator<'a> {
+    fn new(grid: &'a Grid<u8>) -> Self {
+        PartNumberIterator {
+            part_number: None,
+            indexed_iter: Box::new(grid.indexed_iter()),
+        }
+    }
+}
+
+fn ascii_to_u64(c: u8) -> u64 {
+    u64::from(c - b'0')
+}
+
+/// Part number at `pos`, or an error if its digits overflowed
+fn part_number((pos, total): (Position, Option<u64>)) -> AocResult<(Position, u64)> {
+    total.map(|total| (pos, total)).with_context(|| {
+        format!(
+            "Part number at row {}, column {} is too large",
+            pos.row + 1,
+            pos.column_start + 1
+        )
+    })
+}
+
+impl<'a> Iterator for PartNumberIterator<'a> {
+    type Item = AocResult<(Position, u64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for ((row_0, col_0), &item) in self.indexed_iter.by_ref() {
+            if let Some((pos, total)) = self.part_number {
+                let Position {
+                    row, column_start, ..
+                } = pos;
+                match (row_0 == row, item.is_ascii_digit()) {
+                    (true, true) => {
+                        let pos_1 = Position {
+                            row,
+                            column_start,
+                            column_end: col_0,
+                        };
+                        let total = total
+                            .and_then(|total| total.checked_mul(10))
+                            .and_then(|total| total.checked_add(ascii_to_u64(item)));
+                        self.part_number = Some((pos_1, total));
+                    }
+                    (false, true) => {
+                        let pos_1 = Position {
+                            row: row_0,
+                            column_start: col_0,
+                            column_end: col_0,
+                        };
+                        self.part_number = Some((pos_1, Some(ascii_to_u64(item))));
+                        return Some(part_number((pos, total)));
+                    }
+                    (true | false, false) => {
+                        self.part_number = None;
+                        return Some(part_number((pos, total)));
+                    }
+                }
+            } else if item.is_ascii_digit() {
+                let pos_1 = Position {
+                    row: row_0,
+                    column_start: col_0,
+                    column_end: col_0,
+                };
+                self.part_number = Some((pos_1, Some(ascii_to_u64(item))));
+            }
+        }
+        self.part_number.take().map(part_number)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InputData(Grid<u8>);
+
+impl ToPuzzleText for InputData {
+    fn to_puzzle_text(&self) -> String {
+        self.0.render(|&c| c as char)
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::combinator::map;
+    let schematic = Grid::parser("digit, symbol or '.'", |c| {
+        u8::try_from(c).ok().filter(u8::is_ascii_graphic)
+    });
+    let mut parser = map(schematic, InputData);
+    parser(input)
+}
+
+fn next_to_symbol(grid: &Grid<u8>, pos: Position) -> bool {
+    (pos.column_start..=pos.column_end)
+        .flat_map(|col| grid.neighbours_8((pos.row, col)))
+        .filter(|&neighbour| !pos.contains(neighbour))
+        .any(|neighbour| grid[neighbour] != b'.')
+}
+
+fn part1(input: &InputData) -> AocResult<u64> {
+    PartNumberIterator::new(&input.0)
+        .filter_ok(|&(pos, _)| next_to_symbol(&input.0, pos))
+        .try_fold(0_u64, |sum, part| {
+            sum.checked_add(part?.1)
+                .context("Sum of part numbers is too large")
+        })
+}
+
+fn part2(input: &InputData) -> AocResult<u64> {
+    let part_numbers: Vec<(Position, u64)> =
+        PartNumberIterator::new(&input.0).collect::<AocResult<_>>()?;
+    input
+        .0
+        .indexed_iter()
+        .filter(|&(_, &item)| item == b'*')
+        .map(|(pos, _)| pos)
+        .filter_map(|gear| {
+            let v: Vec<(Position, u64)> = input
+                .0
+                .neighbours_8(gear)
+                .filter_map(|neighbour| {
+                    part_numbers
+                        .iter()
+                        .find(|(pos, _)| pos.contains(neighbour))
+                        .copied()
+                })
+                .unique()
+                .collect();
+            if v.len() == 2 {
+                Some(v[0].1.checked_mul(v[1].1))
+            } else {
+                None
+            }
+        })
+        .try_fold(0_u64, |sum, ratio| {
+            ratio
+                .and_then(|ratio| sum.checked_add(ratio))
+                .context("Sum of gear ratios is too large")
+        })
+}
+
+const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const EXAMPLES: &'static [&'static str] = &[INPUT];
+
+    type Input = InputData;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<u64> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<u64> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData(Grid::from([
+                [52, 54, 55, 46, 46, 49, 49, 52, 46, 46],
+                [46, 46, 46, 42, 46, 46, 46, 46, 46, 46],
+                [46, 46, 51, 53, 46, 46, 54, 51, 51, 46],
+                [46, 46, 46, 46, 46, 46, 35, 46, 46, 46],
+                [54, 49, 55, 42, 46, 46, 46, 46, 46, 46],
+                [46, 46, 46, 46, 46, 43, 46, 53, 56, 46],
+                [46, 46, 53, 57, 50, 46, 46, 46, 46, 46],
+                [46, 46, 46, 46, 46, 46, 55, 53, 53, 46],
+                [46, 46, 46, 36, 46, 42, 46, 46, 46, 46],
+                [46, 54, 54, 52, 46, 53, 57, 56, 46, 46]
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip!(parse, INPUT);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 4361);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 467835);
+    }
+
+    #[test]
+    fn test_large_part_number() {
+        let input = "18446744073709551615*\n";
+        assert_part!(parse, part1, input, u64::MAX);
+        let input = "1*18446744073709551616\n";
+        let input = finish(input, parse(input)).unwrap();
+        assert_eq!(
+            part1(&input).unwrap_err().to_string(),
+            "Part number at row 1, column 3 is too large"
+        );
+        assert_eq!(
+            part2(&input).unwrap_err().to_string(),
+            "Part number at row 1, column 3 is too large"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::{btree_set::Intersection, BTreeSet};
+
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Card {
+    id: u8,
+    numbers: BTreeSet<u8>,
+    winning: BTreeSet<u8>,
+}
+
+impl<'a> Card {
+    pub fn matching_numbers(&'a self) -> Intersection<'a, u8> {
+        self.winning.intersection(&self.numbers)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    cards: Vec<Card>,
+}
+
+impl ToPuzzleText for InputData {
+    fn to_puzzle_text(&self) -> String {
+        let numbers = |numbers: &BTreeSet<u8>| {
+            let numbers: Vec<String> = numbers.iter().map(|n| format!("{n:>2}")).collect();
+            numbers.join(" ")
+        };
+        let cards: Vec<String> = self
+            .cards
+            .iter()
+            .map(|card| {
+                format!(
+                    "Card {}: {} | {}",
+                    card.id,
+                    numbers(&card.winning),
+                    numbers(&card.numbers)
+                )
+            })
+            .collect();
+        cards.join("\n")
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{line_ending, space1, u8},
+        combinator::map,
+        error::context,
+        multi::separated_list1,
+        sequence::{delimited, pair, separated_pair, tuple},
+    };
+
+    let card_id = delimited(pair(tag("Card"), space1), u8, pair(tag(":"), space1));
+    let winning_numbers = map(separated_list1(space1, u8), |v| v.into_iter().collect());
+    let card_numbers = map(separated_list1(space1, u8), |v| v.into_iter().collect());
+    let numbers = separated_pair(
+        winning_numbers,
+        tuple((space1, tag("|"), space1)),
+        card_numbers,
+    );
+    let card = context(
+        "card",
+        map(pair(card_id, numbers), |(id, (winning, numbers))| Card {
+            id,
+            numbers,
+            winning,
+        }),
+    );
+    let cards = separated_list1(line_ending, card);
+    let mut parser = map(cards, |cards| InputData { cards });
+    parser(input)
+}
+
+fn part1(input: &InputData) -> AocResult<u64> {
+    input
+        .cards
+        .iter()
+        .map(|card| {
+            let count = u32::try_from(card.matching_numbers().count())?;
+            count.checked_sub(1).map_or(Ok(0), |j| {
+                2_u64
+                    .checked_pow(j)
+                    .with_context(|| format!("Card {} is worth too many points", card.id))
+            })
+        })
+        .sum()
+}
+
+fn part2(input: &InputData) -> AocResult<usize> {
+    let mut values: Vec<(usize, Card)> = input.cards.iter().cloned().map(|c| (1, c)).collect();
+    let mut total: usize = 0;
+
+    for i in 0..values.len() {
+        let (card_count, card) = values[i].clone();
+        let win_count = card.matching_numbers().count();
+        if win_count >= values.len() - i {
+            bail!("Card {} wins copies of cards past the end", card.id);
+        }
+        for j in 1..=win_count {
+            values[i + j].0 += card_count;
+        }
+        total += card_count;
+    }
+
+    Ok(total)
+}
+
+const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const EXAMPLES: &'static [&'static str] = &[INPUT];
+
+    type Input = InputData;
+    type Output1 = u64;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<u64> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                cards: vec![
+                    Card {
+                        id: 1,
+                        winning: [41, 48, 83, 86, 17].into(),
+                        numbers: [83, 86, 6, 31, 17, 9, 48, 53].into()
+                    },
+                    Card {
+                        id: 2,
+                        winning: [13, 32, 20, 16, 61].into(),
+                        numbers: [61, 30, 68, 82, 17, 32, 24, 19].into()
+                    },
+                    Card {
+                        id: 3,
+                        winning: [1, 21, 53, 59, 44].into(),
+                        numbers: [69, 82, 63, 72, 16, 21, 14, 1].into()
+                    },
+                    Card {
+                        id: 4,
+                        winning: [41, 92, 73, 84, 69].into(),
+                        numbers: [59, 84, 76, 51, 58, 5, 54, 83].into()
+                    },
+                    Card {
+                        id: 5,
+                        winning: [87, 83, 26, 28, 32].into(),
+                        numbers: [88, 30, 70, 12, 93, 22, 82, 36].into()
+                    },
+                    Card {
+                        id: 6,
+                        winning: [31, 18, 13, 56, 72].into(),
+                        numbers: [74, 77, 10, 23, 35, 67, 36, 11].into()
+                    }
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip!(parse, INPUT);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 30);
+    }
+
+    #[test]
+    fn test_copies_past_the_end() {
+        let input = finish("Card 1: 1 2 | 1 2", parse("Card 1: 1 2 | 1 2")).unwrap();
+        assert_eq!(part1(&input).unwrap(), 2);
+        assert_eq!(
+            part2(&input).unwrap_err().to_string(),
+            "Card 1 wins copies of cards past the end"
+        );
+    }
+}
@@ -0,0 +1,849 @@
+use std::ops::Range;
+
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Mapper {
+    length: u64,
+    source: u64,
+    destination: u64,
+}
+
+impl Mapper {
+    /// Value `i` maps to, or `None` if that does not fit in a `u64`
+    #[inline]
+    pub const fn map_to_destination(&self, i: u64) -> Option<u64> {
+        if self.in_range(i) {
+            let dist = i - self.source;
+            self.destination.checked_add(dist)
+        } else {
+            Some(i)
+        }
+    }
+
+    #[inline]
+    pub const fn in_range(&self, i: u64) -> bool {
+        self.source <= i && i - self.source < self.length
+    }
+
+    /// First source after the ones this mapper covers
+    const fn source_end(&self) -> u64 {
+        self.source.saturating_add(self.length)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Lookup(Vec<Mapper>);
+
+impl Lookup {
+    #[inline]
+    pub fn map_to_destination(&self, i: u64) -> Option<u64> {
+        self.0
+            .iter()
+            .find(|mapper| mapper.in_range(i))
+            .map_or(Some(i), |mapper| mapper.map_to_destination(i))
+    }
+
+    /// Values that map to `i`
+    ///
+    /// These are the sources of the mappers that move values to `i`, and
+    /// `i` itself when no mapper covers it.
+    pub fn sources(&self, i: u64) -> Vec<u64> {
+        let moved = self.0.iter().filter_map(|mapper| {
+            let source = i
+                .checked_sub(mapper.destination)?
+                .checked_add(mapper.source)?;
+            (mapper.in_range(source) && self.map_to_destination(source) == Some(i))
+                .then_some(source)
+        });
+        let kept = (self.map_to_destination(i) == Some(i)).then_some(i);
+        moved.chain(kept).collect()
+    }
+
+    /// Maps every value of `ranges`, split where the mappers start and end
+    ///
+    /// Like for single values the first mapper that covers a value wins,
+    /// and values no mapper covers keep their number. The ranges come out
+    /// in no particular order, or not at all if a value maps past `u64::MAX`.
+    pub fn map_ranges(&self, ranges: Vec<Range<u64>>) -> Option<Vec<Range<u64>>> {
+        let mut mapped = Vec::new();
+        let mut unmapped = ranges;
+        for mapper in &self.0 {
+            let mut rest = Vec::new();
+            for range in unmapped {
+                let start = range.start.max(mapper.source);
+                let end = range.end.min(mapper.source_end());
+                if start < end {
+                    let destination = mapper.map_to_destination(start)?;
+                    mapped.push(destination..destination.checked_add(end - start)?);
+                    if range.start < start {
+                        rest.push(range.start..start);
+                    }
+                    if end < range.end {
+                        rest.push(end..range.end);
+                    }
+                } else {
+                    rest.push(range);
+                }
+            }
+            unmapped = rest;
+        }
+        mapped.extend(unmapped);
+        Some(mapped)
+    }
+
+    /// Same mapping as sorted mappers that do not overlap
+    ///
+    /// Where the mappers overlap the first one wins, as it does when
+    /// mapping single values.
+    pub fn flatten(&self) -> Option<Piecewise> {
+        let mut bounds: Vec<u64> = self
+            .0
+            .iter()
+            .flat_map(|mapper| [mapper.source, mapper.source_end()])
+            .collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+        let pieces = bounds
+            .windows(2)
+            .filter_map(|bounds| {
+                let mapper = self.0.iter().find(|mapper| mapper.in_range(bounds[0]))?;
+                Some(
+                    mapper
+                        .map_to_destination(bounds[0])
+                        .map(|destination| Mapper {
+                            length: bounds[1] - bounds[0],
+                            source: bounds[0],
+                            destination,
+                        }),
+                )
+            })
+            .collect::<Option<_>>()?;
+        Some(Piecewise(pieces))
+    }
+
+    /// Lookup that maps with `self` and then with `next`
+    pub fn compose(&self, next: &Self) -> Option<Piecewise> {
+        self.flatten()?.compose(&next.flatten()?)
+    }
+}
+
+/// Mapping whose mappers are sorted by source and do not overlap
+///
+/// A value is mapped with a binary search for its mapper, and values that
+/// no mapper covers keep their number like in a [`Lookup`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Piecewise(Vec<Mapper>);
+
+impl Piecewise {
+    pub fn map_to_destination(&self, i: u64) -> Option<u64> {
+        let after = self.0.partition_point(|mapper| mapper.source <= i);
+        self.0[..after]
+            .last()
+            .map_or(Some(i), |mapper| mapper.map_to_destination(i))
+    }
+
+    /// Parts of `range` split where the mappers start and end, in order,
+    /// with the value each part's start maps to
+    fn segments(&self, range: Range<u64>) -> Option<Vec<(Range<u64>, u64)>> {
+        let mut segments = Vec::new();
+        let mut start = range.start;
+        let first = self
+            .0
+            .partition_point(|mapper| mapper.source_end() <= start);
+        for mapper in &self.0[first..] {
+            if mapper.source >= range.end {
+                break;
+            }
+            if start < mapper.source {
+                segments.push((start..mapper.source, start));
+                start = mapper.source;
+            }
+            let end = mapper.source_end().min(range.end);
+            segments.push((start..end, mapper.map_to_destination(start)?));
+            start = end;
+        }
+        if start < range.end {
+            segments.push((start..range.end, start));
+        }
+        Some(segments)
+    }
+
+    /// Mapping that maps with `self` and then with `next`
+    ///
+    /// Values below `u64::MAX` are covered, which is every value an
+    /// almanac can list. This is `None` if a value maps past `u64::MAX`.
+    pub fn compose(&self, next: &Self) -> Option<Self> {
+        let mut pieces = Vec::new();
+        for (range, destination) in self.segments(0..u64::MAX)? {
+            let length = range.end - range.start;
+            for (part, end) in next.segments(destination..destination.checked_add(length)?)? {
+                let source = range.start + (part.start - destination);
+                if source != end {
+                    pieces.push(Mapper {
+                        length: part.end - part.start,
+                        source,
+                        destination: end,
+                    });
+                }
+            }
+        }
+        Some(Self(pieces))
+    }
+
+    /// Mapping back from destinations to sources
+    ///
+    /// This is `None` when two values map to the same one, because there
+    /// is no single source to map back to then, or when a value maps past
+    /// `u64::MAX`.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut pieces: Vec<Mapper> = self
+            .segments(0..u64::MAX)?
+            .into_iter()
+            .map(|(range, destination)| Mapper {
+                length: range.end - range.start,
+                source: destination,
+                destination: range.start,
+            })
+            .collect();
+        pieces.sort_unstable_by_key(|mapper| mapper.source);
+        if pieces
+            .windows(2)
+            .any(|pair| pair[0].source_end() > pair[1].source)
+        {
+            return None;
+        }
+        pieces.retain(|mapper| mapper.source != mapper.destination);
+        Some(Self(pieces))
+    }
+}
+
+/// The mappers of a [`Piecewise`] never overlap, so the order they are
+/// tried in does not matter
+impl From<Piecewise> for Lookup {
+    fn from(Piecewise(mappers): Piecewise) -> Self {
+        Self(mappers)
+    }
+}
+
+/// Map from one category of the almanac to the next
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Stage {
+    from: String,
+    to: String,
+    lookup: Lookup,
+}
+
+impl Stage {
+    fn new(from: &str, to: &str, mappers: Vec<Mapper>) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            lookup: Lookup(mappers),
+        }
+    }
+}
+
+/// Chain of maps, each starting from the category the one before maps to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Almanac {
+    stages: Vec<Stage>,
+}
+
+impl Almanac {
+    /// Categories in the order the maps go through them
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        self.stages
+            .first()
+            .map(|stage| stage.from.as_str())
+            .into_iter()
+            .chain(self.stages.iter().map(|stage| stage.to.as_str()))
+    }
+
+    /// Stages that lead from category `from` to category `to`
+    fn stages(&self, from: &str, to: &str) -> AocResult<&[Stage]> {
+        let position = |category| {
+            self.categories()
+                .position(|c| c == category)
+                .ok_or_else(|| anyhow!("No {category} in the almanac"))
+        };
+        let (start, end) = (position(from)?, position(to)?);
+        if start > end {
+            bail!("The almanac maps {to} to {from}, not {from} to {to}");
+        }
+        Ok(&self.stages[start..end])
+    }
+
+    /// Value of category `to` that `value` of category `from` corresponds to
+    pub fn map(&self, from: &str, to: &str, value: u64) -> AocResult<u64> {
+        self.stages(from, to)?
+            .iter()
+            .try_fold(value, |value, stage| stage.lookup.map_to_destination(value))
+            .with_context(|| format!("{from} {value} maps past {}", u64::MAX))
+    }
+
+    /// Single mapping from category `from` to category `to`
+    pub fn lookup(&self, from: &str, to: &str) -> AocResult<Piecewise> {
+        self.stages(from, to)?
+            .iter()
+            .try_fold(Lookup::default(), |lookup, stage| {
+                lookup.compose(&stage.lookup).map(Lookup::from)
+            })
+            .and_then(|lookup| lookup.flatten())
+            .with_context(|| format!("The map from {from} to {to} goes past {}", u64::MAX))
+    }
+
+    /// Values of category `from` that correspond to `value` of category `to`, in order
+    pub fn inverse(&self, from: &str, to: &str, value: u64) -> AocResult<Vec<u64>> {
+        if let Some(inverse) = self.lookup(from, to)?.inverse() {
+            if let Some(value) = inverse.map_to_destination(value) {
+                return Ok(vec![value]);
+            }
+        }
+        let mut values = self
+            .stages(from, to)?
+            .iter()
+            .rev()
+            .fold(vec![value], |values, stage| {
+                values
+                    .into_iter()
+                    .flat_map(|value| stage.lookup.sources(value))
+                    .collect()
+            });
+        values.sort_unstable();
+        values.dedup();
+        Ok(values)
+    }
+
+    /// Ranges of category `to` that `ranges` of category `from` correspond to
+    pub fn map_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: Vec<Range<u64>>,
+    ) -> AocResult<Vec<Range<u64>>> {
+        self.stages(from, to)?
+            .iter()
+            .try_fold(ranges, |ranges, stage| stage.lookup.map_ranges(ranges))
+            .with_context(|| format!("The {from} ranges map past {}", u64::MAX))
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct InputData {
+    seeds: Vec<u64>,
+    almanac: Almanac,
+}
+
+impl InputData {
+    #[must_use]
+    pub const fn almanac(&self) -> &Almanac {
+        &self.almanac
+    }
+}
+
+impl ToPuzzleText for InputData {
+    fn to_puzzle_text(&self) -> String {
+        let seeds: Vec<String> = self.seeds.iter().map(u64::to_string).collect();
+        let mut sections = vec![format!("seeds: {}", seeds.join(" "))];
+        for Stage { from, to, lookup } in &self.almanac.stages {
+            let mappers: Vec<String> = lookup
+                .0
+                .iter()
+                .map(|m| format!("{} {} {}", m.destination, m.source, m.length))
+                .collect();
+            sections.push(format!("{from}-to-{to} map:\n{}", mappers.join("\n")));
+        }
+        sections.join("\n\n")
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{alpha1, line_ending, space1, u64},
+        combinator::{consumed, map},
+        error::{context, VerboseError, VerboseErrorKind},
+        multi::separated_list1,
+        sequence::{pair, preceded, separated_pair, terminated, tuple},
+    };
+    let seeds = context(
+        "seeds",
+        preceded(tag("seeds: "), separated_list1(space1, u64)),
+    );
+    let mapper = |input| {
+        let (rest, (destination, _, source, _, length)) =
+            tuple((u64, space1, u64, space1, u64))(input)?;
+        if destination.checked_add(length).is_none() || source.checked_add(length).is_none() {
+            return Err(nom::Err::Failure(VerboseError {
+                errors: vec![(
+                    input,
+                    VerboseErrorKind::Context("ranges that end within u64"),
+                )],
+            }));
+        }
+        let mapper = Mapper {
+            length,
+            source,
+            destination,
+        };
+        Ok((rest, mapper))
+    };
+    let mappers = separated_list1(line_ending, mapper);
+    let header = context(
+        "map name",
+        terminated(
+            separated_pair(alpha1, tag("-to-"), alpha1),
+            pair(tag(" map:"), line_ending),
+        ),
+    );
+    let stage = context(
+        "map",
+        map(pair(header, mappers), |((from, to), mappers)| {
+            Stage::new(from, to, mappers)
+        }),
+    );
+    let mut stages = separated_list1(pair(line_ending, line_ending), consumed(stage));
+    let almanac = |input| {
+        let (rest, stages) = stages(input)?;
+        for pair in stages.windows(2) {
+            let ((_, previous), (text, stage)) = (&pair[0], &pair[1]);
+            if stage.from != previous.to {
+                return Err(nom::Err::Failure(VerboseError {
+                    errors: vec![(
+                        *text,
+                        VerboseErrorKind::Context("map from the last category"),
+                    )],
+                }));
+            }
+        }
+        let stages = stages.into_iter().map(|(_, stage)| stage).collect();
+        Ok((rest, Almanac { stages }))
+    };
+    let mut parser = map(
+        separated_pair(seeds, pair(line_ending, line_ending), almanac),
+        |(seeds, almanac)| InputData { seeds, almanac },
+    );
+
+    parser(input)
+}
+
+fn part1(InputData { seeds, almanac }: &InputData) -> AocResult<u64> {
+    let lookup = almanac.lookup("seed", "location")?;
+    let locations = seeds
+        .iter()
+        .map(|&seed| {
+            lookup
+                .map_to_destination(seed)
+                .with_context(|| format!("Seed {seed} maps past {}", u64::MAX))
+        })
+        .collect::<AocResult<Vec<u64>>>()?;
+    locations.into_iter().min().context("No seeds")
+}
+
+fn part2(InputData { seeds, almanac }: &InputData) -> AocResult<u64> {
+    if seeds.len() % 2 != 0 {
+        bail!(
+            "Seed ranges need a start and a length, got {} numbers",
+            seeds.len()
+        );
+    }
+    let seeds = seeds
+        .chunks(2)
+        .map(|v| {
+            let end = v[0]
+                .checked_add(v[1])
+                .with_context(|| format!("Seed range {} {} ends past {}", v[0], v[1], u64::MAX))?;
+            Ok(v[0]..end)
+        })
+        .collect::<AocResult<_>>()?;
+    almanac
+        .map_ranges("seed", "location", seeds)?
+        .into_iter()
+        .filter(|locations| !locations.is_empty())
+        .map(|locations| locations.start)
+        .min()
+        .context("No seeds")
+}
+
+const INPUT: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const EXAMPLES: &'static [&'static str] = &[INPUT];
+
+    type Input = InputData;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<u64> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<u64> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                seeds: vec![79, 14, 55, 13],
+                almanac: Almanac {
+                    stages: vec![
+                        Stage::new(
+                            "seed",
+                            "soil",
+                            vec![
+                                Mapper {
+                                    length: 2,
+                                    source: 98,
+                                    destination: 50
+                                },
+                                Mapper {
+                                    length: 48,
+                                    source: 50,
+                                    destination: 52
+                                }
+                            ]
+                        ),
+                        Stage::new(
+                            "soil",
+                            "fertilizer",
+                            vec![
+                                Mapper {
+                                    length: 37,
+                                    source: 15,
+                                    destination: 0
+                                },
+                                Mapper {
+                                    length: 2,
+                                    source: 52,
+                                    destination: 37
+                                },
+                                Mapper {
+                                    length: 15,
+                                    source: 0,
+                                    destination: 39
+                                }
+                            ]
+                        ),
+                        Stage::new(
+                            "fertilizer",
+                            "water",
+                            vec![
+                                Mapper {
+                                    length: 8,
+                                    source: 53,
+                                    destination: 49
+                                },
+                                Mapper {
+                                    length: 42,
+                                    source: 11,
+                                    destination: 0
+                                },
+                                Mapper {
+                                    length: 7,
+                                    source: 0,
+                                    destination: 42
+                                },
+                                Mapper {
+                                    length: 4,
+                                    source: 7,
+                                    destination: 57
+                                }
+                            ]
+                        ),
+                        Stage::new(
+                            "water",
+                            "light",
+                            vec![
+                                Mapper {
+                                    length: 7,
+                                    source: 18,
+                                    destination: 88
+                                },
+                                Mapper {
+                                    length: 70,
+                                    source: 25,
+                                    destination: 18
+                                }
+                            ]
+                        ),
+                        Stage::new(
+                            "light",
+                            "temperature",
+                            vec![
+                                Mapper {
+                                    length: 23,
+                                    source: 77,
+                                    destination: 45
+                                },
+                                Mapper {
+                                    length: 19,
+                                    source: 45,
+                                    destination: 81
+                                },
+                                Mapper {
+                                    length: 13,
+                                    source: 64,
+                                    destination: 68
+                                }
+                            ]
+                        ),
+                        Stage::new(
+                            "temperature",
+                            "humidity",
+                            vec![
+                                Mapper {
+                                    length: 1,
+                                    source: 69,
+                                    destination: 0
+                                },
+                                Mapper {
+                                    length: 69,
+                                    source: 0,
+                                    destination: 1
+                                }
+                            ]
+                        ),
+                        Stage::new(
+                            "humidity",
+                            "location",
+                            vec![
+                                Mapper {
+                                    length: 37,
+                                    source: 56,
+                                    destination: 60
+                                },
+                                Mapper {
+                                    length: 4,
+                                    source: 93,
+                                    destination: 56
+                                }
+                            ]
+                        )
+                    ]
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip!(parse, INPUT);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 35);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 46);
+    }
+
+    #[test]
+    fn test_map_ranges() {
+        let lookup = Lookup(vec![
+            Mapper {
+                length: 2,
+                source: 98,
+                destination: 50,
+            },
+            Mapper {
+                length: 48,
+                source: 50,
+                destination: 52,
+            },
+        ]);
+        let mut ranges = lookup.map_ranges(vec![0..10, 45..55, 97..101]).unwrap();
+        ranges.sort_by_key(|r| r.start);
+        assert_eq!(ranges, [0..10, 45..50, 50..52, 52..57, 99..100, 100..101]);
+    }
+
+    #[test]
+    fn test_almanac() {
+        let input = finish(INPUT, parse(INPUT)).unwrap();
+        let almanac = input.almanac();
+        assert_eq!(almanac.categories().count(), 8);
+        assert_eq!(almanac.map("seed", "soil", 79).unwrap(), 81);
+        assert_eq!(almanac.map("seed", "location", 79).unwrap(), 82);
+        assert_eq!(almanac.map("soil", "soil", 79).unwrap(), 79);
+        assert_eq!(almanac.inverse("seed", "location", 82).unwrap(), [79]);
+        assert_eq!(almanac.inverse("seed", "soil", 50).unwrap(), [98]);
+        assert_eq!(almanac.inverse("seed", "soil", 10).unwrap(), [10]);
+        assert_eq!(
+            almanac.map("location", "seed", 82).unwrap_err().to_string(),
+            "The almanac maps seed to location, not location to seed"
+        );
+        assert!(almanac.map("seed", "gold", 82).is_err());
+
+        let mut rng = random::Rng::new(5);
+        for _ in 0..50 {
+            let input = random::almanac(&mut rng, 100);
+            let InputData { seeds, almanac } = finish(&input, parse(&input)).unwrap();
+            for seed in seeds {
+                let location = almanac.map("seed", "location", seed).unwrap();
+                let sources = almanac.inverse("seed", "location", location).unwrap();
+                assert!(sources.contains(&seed), "{input}");
+                for source in sources {
+                    assert_eq!(almanac.map("seed", "location", source).unwrap(), location);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_broken_chain() {
+        let input = "seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n\nwater-to-light map:\n1 2 3\n";
+        assert_eq!(
+            finish(input, parse(input)).unwrap_err().to_string(),
+            "line 6, column 1: expected map from the last category\n  |\n6 | water-to-light map:\n  | ^"
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = "seeds: 1 2\n\nseed-to-location map:\n18446744073709551615 0 5";
+        assert!(finish(input, parse(input))
+            .unwrap_err()
+            .to_string()
+            .starts_with("line 4, column 1: expected ranges that end within u64 (in map)\n"));
+        let input = "seeds: 1 2\n\nseed-to-location map:\n0 18446744073709551615 1";
+        assert!(finish(input, parse(input)).is_err());
+
+        let lookup = Lookup(vec![Mapper {
+            length: 5,
+            source: 0,
+            destination: u64::MAX,
+        }]);
+        assert_eq!(lookup.map_to_destination(0), Some(u64::MAX));
+        assert_eq!(lookup.map_to_destination(1), None);
+        assert_eq!(lookup.map_ranges(vec![0..2, 3..4]), None);
+        assert_eq!(lookup.compose(&Lookup::default()), None);
+    }
+
+    #[test]
+    fn test_compose() {
+        let InputData { seeds, almanac } = finish(INPUT, parse(INPUT)).unwrap();
+        let lookup = almanac.lookup("seed", "location").unwrap();
+        let locations: Vec<u64> = seeds
+            .iter()
+            .map(|&s| lookup.map_to_destination(s).unwrap())
+            .collect();
+        assert_eq!(locations, [82, 43, 86, 35]);
+        let inverse = lookup.inverse().unwrap();
+        assert_eq!(inverse.map_to_destination(35), Some(13));
+        assert_eq!(inverse.compose(&lookup), Some(Piecewise::default()));
+
+        let stages = &almanac.stages;
+        let soil_to_water = stages[1].lookup.compose(&stages[2].lookup).unwrap();
+        for soil in 0..120 {
+            assert_eq!(
+                soil_to_water.map_to_destination(soil).unwrap(),
+                almanac.map("soil", "water", soil).unwrap()
+            );
+        }
+
+        let overlapping = Lookup(vec![
+            Mapper {
+                length: 5,
+                source: 0,
+                destination: 10,
+            },
+            Mapper {
+                length: 5,
+                source: 10,
+                destination: 0,
+            },
+            Mapper {
+                length: 2,
+                source: 20,
+                destination: 3,
+            },
+        ]);
+        assert!(overlapping.flatten().unwrap().inverse().is_none());
+    }
+
+    #[test]
+    fn test_random_compose() {
+        let mut rng = random::Rng::new(23);
+        for _ in 0..50 {
+            let input = random::almanac(&mut rng, 100);
+            let InputData { almanac, .. } = finish(&input, parse(&input)).unwrap();
+            let lookup = almanac.lookup("seed", "location").unwrap();
+            let inverse = lookup.inverse();
+            for seed in 0..120 {
+                let location = almanac.map("seed", "location", seed).unwrap();
+                assert_eq!(lookup.map_to_destination(seed), Some(location), "{input}");
+                if let Some(inverse) = &inverse {
+                    assert_eq!(inverse.map_to_destination(location), Some(seed), "{input}");
+                }
+            }
+        }
+    }
+
+    /// Runs part 1 on every seed of the ranges
+    fn part2_from_part1(InputData { seeds, almanac }: &InputData) -> AocResult<u64> {
+        let seeds = seeds.chunks(2).flat_map(|v| v[0]..v[0] + v[1]).collect();
+        part1(&InputData {
+            seeds,
+            almanac: almanac.clone(),
+        })
+    }
+
+    #[test]
+    fn test_part2_brute_force() {
+        assert_equivalent!(parse, part2, part2_from_part1, |rng| {
+            random::almanac(rng, 200)
+        });
+    }
+}
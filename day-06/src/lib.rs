@@ -0,0 +1,164 @@
+use std::fmt::Write;
+
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RaceStat {
+    time: u64,
+    record: u64,
+}
+
+impl RaceStat {
+    fn margins(&self) -> usize {
+        let mut margin_iter = (1..=self.time).filter(|held_time| {
+            let run_time = self.time - held_time;
+            let distance = held_time * run_time;
+            distance > self.record
+        });
+        let Some(start) = margin_iter.next() else {
+            return 0;
+        };
+        let end = margin_iter.next_back().unwrap_or(start);
+        usize::try_from(end - start + 1).unwrap_or(usize::MAX)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputData(Vec<RaceStat>);
+
+impl ToPuzzleText for InputData {
+    fn to_puzzle_text(&self) -> String {
+        let mut times = String::from("Time:    ");
+        let mut distances = String::from("Distance:");
+        for RaceStat { time, record } in &self.0 {
+            let width = time.to_string().len().max(record.to_string().len()) + 2;
+            let _ = write!(times, "{time:>width$}");
+            let _ = write!(distances, "{record:>width$}");
+        }
+        format!("{times}\n{distances}")
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{line_ending, space1, u64},
+        combinator::map,
+        error::context,
+        multi::separated_list1,
+        sequence::{pair, preceded, separated_pair},
+    };
+    let times = context(
+        "times",
+        preceded(pair(tag("Time:"), space1), separated_list1(space1, u64)),
+    );
+    let distances = context(
+        "distances",
+        preceded(pair(tag("Distance:"), space1), separated_list1(space1, u64)),
+    );
+    let mut parser = map(
+        separated_pair(times, line_ending, distances),
+        |(times, distances)| {
+            InputData(
+                times
+                    .into_iter()
+                    .zip(distances)
+                    .map(|(time, record)| RaceStat { time, record })
+                    .collect(),
+            )
+        },
+    );
+    parser(input)
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part1(InputData(races): &InputData) -> AocResult<usize> {
+    Ok(races.iter().map(RaceStat::margins).product())
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part2(InputData(races): &InputData) -> AocResult<usize> {
+    let mut time: String = String::new();
+    let mut record: String = String::new();
+    for r in races {
+        time += &r.time.to_string();
+        record += &r.record.to_string();
+    }
+    let race = RaceStat {
+        time: time.parse()?,
+        record: record.parse()?,
+    };
+    Ok(race.margins())
+}
+
+const INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const EXAMPLES: &'static [&'static str] = &[INPUT];
+
+    type Input = InputData;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData(vec![
+                RaceStat { time: 7, record: 9 },
+                RaceStat {
+                    time: 15,
+                    record: 40
+                },
+                RaceStat {
+                    time: 30,
+                    record: 200
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip!(parse, INPUT);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 288);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 71503);
+    }
+
+    #[test]
+    fn test_margins() {
+        assert_part!(parse, part1, "Time: 4\nDistance: 3", 1);
+        assert_part!(parse, part1, "Time: 4\nDistance: 4", 0);
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug, PartialEq)]
+struct Node {
+    left: String,
+    right: String,
+}
+
+impl Node {
+    pub fn go_dir(&self, dir: Direction) -> String {
+        match dir {
+            Direction::Left => self.left.clone(),
+            Direction::Right => self.right.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    directions: Vec<Direction>,
+    nodes: HashMap<String, Node>,
+}
+
+impl ToPuzzleText for InputData {
+    fn to_puzzle_text(&self) -> String {
+        let directions: String = self
+            .directions
+            .iter()
+            .map(|direction| match direction {
+                Direction::Left => 'L',
+                Direction::Right => 'R',
+            })
+            .collect();
+        let mut nodes: Vec<String> = self
+            .nodes
+            .iter()
+            .map(|(label, Node { left, right })| format!("{label} = ({left}, {right})"))
+            .collect();
+        nodes.sort();
+        format!("{directions}\n\n{}", nodes.join("\n"))
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{alphanumeric1, line_ending},
+        combinator::map,
+        error::context,
+        multi::{many1, separated_list1},
+        sequence::{delimited, pair, separated_pair},
+    };
+    let right = map(tag("R"), |_| Direction::Right);
+    let left = map(tag("L"), |_| Direction::Left);
+    let directions = context("directions", many1(alt((right, left))));
+    let node = map(
+        delimited(
+            tag("("),
+            separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+            tag(")"),
+        ),
+        |(left, right): (&str, &str)| Node {
+            left: left.to_string(),
+            right: right.to_string(),
+        },
+    );
+    let node = context("node", separated_pair(alphanumeric1, tag(" = "), node));
+    let nodes = map(separated_list1(line_ending, node), |v| {
+        v.into_iter().map(|(s, n)| (s.to_string(), n)).collect()
+    });
+    let mut parser = map(
+        separated_pair(directions, pair(line_ending, line_ending), nodes),
+        |(directions, nodes)| InputData { directions, nodes },
+    );
+    parser(input)
+}
+
+/// Steps from `label` until a node passes `ending`
+///
+/// Once every node has been visited at every point of the directions
+/// the walk is going round in circles, so it gives up there.
+fn calc_cycles(
+    label: impl Into<String>,
+    nodes: &HashMap<String, Node>,
+    directions: &[Direction],
+    ending: fn(&str) -> bool,
+) -> AocResult<usize> {
+    let start: String = label.into();
+    let mut current_label = start.clone();
+    let limit = nodes.len() * directions.len();
+    for (count, direction) in directions.iter().cycle().enumerate() {
+        if ending(&current_label) {
+            return Ok(count);
+        }
+        if count > limit {
+            break;
+        }
+        current_label = nodes
+            .get(&current_label)
+            .ok_or_else(|| anyhow!("No node {current_label}"))?
+            .go_dir(*direction);
+    }
+    bail!("No end can be reached from {start}")
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a * b / gcd(a, b)
+}
+
+fn part1(InputData { directions, nodes }: &InputData) -> AocResult<usize> {
+    calc_cycles("AAA", nodes, directions, |label| label == "ZZZ")
+}
+
+fn part2(InputData { directions, nodes }: &InputData) -> AocResult<usize> {
+    nodes
+        .keys()
+        .filter(|label| label.ends_with('A'))
+        .map(|label| calc_cycles(label, nodes, directions, |l| l.ends_with('Z')))
+        .try_fold(1_usize, |total, cycles| Ok(lcm(total, cycles?)))
+}
+
+const INPUT_1: &str = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+";
+const INPUT_2: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+const INPUT_3: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const EXAMPLES: &'static [&'static str] = &[INPUT_1, INPUT_2, INPUT_3];
+
+    type Input = InputData;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT_1,
+            InputData {
+                directions: vec![Direction::Right, Direction::Left],
+                nodes: HashMap::from([
+                    (
+                        "ZZZ".to_string(),
+                        Node {
+                            left: "ZZZ".to_string(),
+                            right: "ZZZ".to_string()
+                        }
+                    ),
+                    (
+                        "AAA".to_string(),
+                        Node {
+                            left: "BBB".to_string(),
+                            right: "CCC".to_string()
+                        }
+                    ),
+                    (
+                        "BBB".to_string(),
+                        Node {
+                            left: "DDD".to_string(),
+                            right: "EEE".to_string()
+                        }
+                    ),
+                    (
+                        "CCC".to_string(),
+                        Node {
+                            left: "ZZZ".to_string(),
+                            right: "GGG".to_string()
+                        }
+                    ),
+                    (
+                        "DDD".to_string(),
+                        Node {
+                            left: "DDD".to_string(),
+                            right: "DDD".to_string()
+                        }
+                    ),
+                    (
+                        "EEE".to_string(),
+                        Node {
+                            left: "EEE".to_string(),
+                            right: "EEE".to_string()
+                        }
+                    ),
+                    (
+                        "GGG".to_string(),
+                        Node {
+                            left: "GGG".to_string(),
+                            right: "GGG".to_string()
+                        }
+                    )
+                ])
+            }
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip!(parse, INPUT_1);
+        assert_round_trip!(parse, INPUT_2);
+        assert_round_trip!(parse, INPUT_3);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT_1, 2);
+        assert_part!(parse, part1, INPUT_2, 6);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT_1, 2);
+        assert_part!(parse, part2, INPUT_2, 6);
+        assert_part!(parse, part2, INPUT_3, 6);
+    }
+
+    #[test]
+    fn test_missing_node() {
+        let input = "L\n\nAAA = (BBB, BBB)\n";
+        let input = finish(input, parse(input)).unwrap();
+        assert_eq!(part1(&input).unwrap_err().to_string(), "No node BBB");
+
+        let input = "L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        let input = finish(input, parse(input)).unwrap();
+        assert_eq!(
+            part1(&input).unwrap_err().to_string(),
+            "No end can be reached from AAA"
+        );
+
+        let input = "L\n\nAAA = (BBB, ZZZ)\nBBB = (BBB, BBB)\nZZZ = (ZZZ, ZZZ)\n";
+        let input = finish(input, parse(input)).unwrap();
+        assert_eq!(
+            part1(&input).unwrap_err().to_string(),
+            "No end can be reached from AAA"
+        );
+    }
+}
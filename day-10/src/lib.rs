@@ -0,0 +1,453 @@
+use utils::grid::Grid;
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+enum Pipe {
+    #[default]
+    None,
+    Start,
+    Vertical,
+    Horizontal,
+    NorthToEast,
+    NorthToWest,
+    SouthToWest,
+    SouthToEast,
+}
+
+impl Pipe {
+    /// Character the tile is drawn with in the puzzle
+    const fn symbol(self) -> char {
+        match self {
+            Self::None => '.',
+            Self::Start => 'S',
+            Self::Vertical => '|',
+            Self::Horizontal => '-',
+            Self::NorthToEast => 'L',
+            Self::NorthToWest => 'J',
+            Self::SouthToWest => '7',
+            Self::SouthToEast => 'F',
+        }
+    }
+
+    const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::None),
+            'S' => Some(Self::Start),
+            '|' => Some(Self::Vertical),
+            '-' => Some(Self::Horizontal),
+            'L' => Some(Self::NorthToEast),
+            'J' => Some(Self::NorthToWest),
+            '7' => Some(Self::SouthToWest),
+            'F' => Some(Self::SouthToEast),
+            _ => None,
+        }
+    }
+
+    const fn valid_from_direction(self, direction: Direction) -> bool {
+        matches!(
+            (self, direction),
+            (Self::Vertical, Direction::North | Direction::South)
+                | (Self::Horizontal, Direction::East | Direction::West)
+                | (Self::NorthToEast, Direction::South | Direction::West)
+                | (Self::NorthToWest, Direction::South | Direction::East)
+                | (Self::SouthToWest, Direction::North | Direction::East)
+                | (Self::SouthToEast, Direction::North | Direction::West)
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// Rows and columns moved by one step this way
+    const fn offset(self) -> (isize, isize) {
+        match self {
+            Self::North => (-1, 0),
+            Self::East => (0, 1),
+            Self::South => (1, 0),
+            Self::West => (0, -1),
+        }
+    }
+
+    const fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+enum Flag {
+    #[default]
+    Empty,
+    Loop,
+    North,
+}
+
+/// Position of a tile of the loop, its pipe and the direction it was entered in
+type Tile = ((usize, usize), Pipe, Direction);
+
+#[derive(Debug)]
+struct PipeIterator<'a> {
+    grid: &'a Grid<Pipe>,
+    direction: Direction,
+    position: (usize, usize),
+    finished: bool,
+}
+
+impl<'a> PipeIterator<'a> {
+    fn try_new(
+        grid: &'a Grid<Pipe>,
+        direction: Direction,
+        position: (usize, usize),
+    ) -> Option<Self> {
+        if grid.get(position) != Some(&Pipe::Start) {
+            return None;
+        }
+        let position = grid.offset(position, direction.offset())?;
+        if grid[position].valid_from_direction(direction) {
+            Some(PipeIterator {
+                grid,
+                direction,
+                position,
+                finished: false,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Moves to the next pipe, turning the way `pipe` leads
+    fn advance(&mut self, pipe: Pipe) -> AocResult<()> {
+        let (row, col) = self.position;
+        self.direction = match (pipe, self.direction) {
+            (Pipe::Vertical | Pipe::Horizontal, direction)
+                if pipe.valid_from_direction(direction) =>
+            {
+                direction
+            }
+            (Pipe::NorthToEast, Direction::South) | (Pipe::SouthToEast, Direction::North) => {
+                Direction::East
+            }
+            (Pipe::NorthToEast, Direction::West) | (Pipe::NorthToWest, Direction::East) => {
+                Direction::North
+            }
+            (Pipe::NorthToWest, Direction::South) | (Pipe::SouthToWest, Direction::North) => {
+                Direction::West
+            }
+            (Pipe::SouthToWest, Direction::East) | (Pipe::SouthToEast, Direction::West) => {
+                Direction::South
+            }
+            (pipe, direction) => bail!(
+                "Loop cannot enter '{}' at row {}, column {} going {direction:?}",
+                pipe.symbol(),
+                row + 1,
+                col + 1
+            ),
+        };
+        self.position = self
+            .grid
+            .offset(self.position, self.direction.offset())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Loop leaves the grid at row {}, column {}",
+                    row + 1,
+                    col + 1
+                )
+            })?;
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for PipeIterator<'a> {
+    type Item = AocResult<Tile>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let current_pipe = self.grid[self.position];
+        let current_direction = self.direction;
+        let position = self.position;
+        if current_pipe == Pipe::Start {
+            self.finished = true;
+        } else if let Err(error) = self.advance(current_pipe) {
+            self.finished = true;
+            return Some(Err(error));
+        }
+        Some(Ok((position, current_pipe, current_direction)))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData(Grid<Pipe>);
+
+impl ToPuzzleText for InputData {
+    fn to_puzzle_text(&self) -> String {
+        self.0.render(|pipe| pipe.symbol())
+    }
+}
+
+impl InputData {
+    fn start(&self) -> AocResult<(usize, usize)> {
+        self.0
+            .position(|&pipe| pipe == Pipe::Start)
+            .context("No start tile")
+    }
+
+    /// Tiles of the loop through the start in order, ending on the start
+    ///
+    /// Pipes next to the start that do not lead back to it are skipped.
+    fn main_loop(&self) -> AocResult<Vec<Tile>> {
+        let start = self.start()?;
+        let mut error = anyhow!("No pipe leads out of the start");
+        for direction in Direction::ALL {
+            if let Some(pipe_iter) = PipeIterator::try_new(&self.0, direction, start) {
+                match pipe_iter.collect() {
+                    Ok(tiles) => return Ok(tiles),
+                    Err(e) => error = e,
+                }
+            }
+        }
+        Err(error)
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::combinator::map;
+    let mut parser = map(Grid::parser("pipe", Pipe::from_char), InputData);
+    parser(input)
+}
+
+fn part1(input: &InputData) -> AocResult<usize> {
+    Ok(input.main_loop()?.len() / 2)
+}
+
+const fn connects_north(pipe: Pipe) -> bool {
+    matches!(pipe, Pipe::Vertical | Pipe::NorthToEast | Pipe::NorthToWest)
+}
+
+/// Pipe hidden under the start, the one that joins both ends of the loop
+fn start_pipe(tiles: &[Tile]) -> Option<Pipe> {
+    let leaves = tiles.first()?.2;
+    let returns = tiles.last()?.2;
+    [
+        Pipe::Vertical,
+        Pipe::Horizontal,
+        Pipe::NorthToEast,
+        Pipe::NorthToWest,
+        Pipe::SouthToWest,
+        Pipe::SouthToEast,
+    ]
+    .into_iter()
+    .find(|pipe| pipe.valid_from_direction(returns) && pipe.valid_from_direction(leaves.opposite()))
+}
+
+fn part2(input: &InputData) -> AocResult<usize> {
+    let mut marker: Grid<Flag> = Grid::filled(input.0.dim(), Flag::default());
+
+    let tiles = input.main_loop()?;
+    let start_north = start_pipe(&tiles).is_some_and(connects_north);
+    for (pos, pipe, _) in tiles {
+        let north = match pipe {
+            Pipe::Start => start_north,
+            pipe => connects_north(pipe),
+        };
+        marker[pos] = if north { Flag::North } else { Flag::Loop };
+    }
+
+    // A tile is inside when an odd number of pipes to its left lead north
+    let mut total: usize = 0;
+    for row in marker.rows() {
+        let mut inside = false;
+        for &flag in row {
+            match flag {
+                Flag::Empty if inside => total += 1,
+                Flag::North => inside = !inside,
+                _ => {}
+            }
+        }
+    }
+    Ok(total)
+}
+
+const INPUT_1: &str = ".....\n.S-7.\n.|.|.\n.L-J.\n.....\n";
+const INPUT_2: &str = "7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ";
+const INPUT_3: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+const INPUT_4: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const EXAMPLES: &'static [&'static str] = &[INPUT_1, INPUT_2, INPUT_3, INPUT_4];
+
+    type Input = InputData;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        use Pipe::*;
+        assert_parser!(
+            parse,
+            INPUT_1,
+            InputData(Grid::from([
+                [None, None, None, None, None],
+                [None, Start, Horizontal, SouthToWest, None],
+                [None, Vertical, None, Vertical, None],
+                [None, NorthToEast, Horizontal, NorthToWest, None],
+                [None, None, None, None, None]
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip!(parse, INPUT_1);
+        assert_round_trip!(parse, INPUT_2);
+        assert_round_trip!(parse, INPUT_3);
+        assert_round_trip!(parse, INPUT_4);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT_1, 4);
+        assert_part!(parse, part1, INPUT_2, 8);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT_3, 4);
+        assert_part!(parse, part2, INPUT_4, 10);
+        // The start closes a loop that runs anticlockwise
+        assert_part!(parse, part2, ".....\n.F-7.\n.|.|.\n.L-S.\n.....", 1);
+    }
+
+    /// Tiles of the loop in order, ending on the start
+    fn loop_tiles(input: &InputData) -> Vec<(usize, usize)> {
+        input
+            .main_loop()
+            .unwrap()
+            .into_iter()
+            .map(|(pos, _, _)| pos)
+            .collect()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn half_loop(input: &InputData) -> AocResult<usize> {
+        Ok(loop_tiles(input).len() / 2)
+    }
+
+    /// Counts the enclosed tiles with the shoelace formula and Pick's theorem
+    #[allow(clippy::unnecessary_wraps)]
+    fn shoelace(input: &InputData) -> AocResult<usize> {
+        let tiles = loop_tiles(input);
+        let twice_area: isize = tiles
+            .iter()
+            .zip(tiles.iter().cycle().skip(1))
+            .map(|(&(r1, c1), &(r2, c2))| {
+                (r1 as isize) * (c2 as isize) - (r2 as isize) * (c1 as isize)
+            })
+            .sum();
+        Ok(twice_area.unsigned_abs() / 2 + 1 - tiles.len() / 2)
+    }
+
+    #[test]
+    fn test_random_loops() {
+        assert_equivalent!(parse, part1, half_loop, |rng| {
+            random::pipe_loop(rng, 8, 10).input
+        });
+        assert_equivalent!(parse, part2, shoelace, |rng| {
+            random::pipe_loop(rng, 8, 10).input
+        });
+        let mut rng = random::Rng::new(10);
+        for _ in 0..16 {
+            let pipes = random::pipe_loop(&mut rng, 6, 6);
+            assert_part!(parse, part1, &pipes.input, pipes.length / 2);
+            assert_part!(parse, part2, &pipes.input, pipes.enclosed);
+        }
+    }
+
+    #[test]
+    fn test_broken_loop() {
+        let error = |input| {
+            let input = finish(input, parse(input)).unwrap();
+            part1(&input).unwrap_err().to_string()
+        };
+        assert_eq!(
+            error("S-7\n|.|\nL-."),
+            "Loop cannot enter '.' at row 3, column 3 going East"
+        );
+        assert_eq!(
+            error("S-7\n|.|\n|.|"),
+            "Loop leaves the grid at row 3, column 1"
+        );
+        assert_eq!(error("..\n.."), "No start tile");
+        assert_eq!(error("S.\n.."), "No pipe leads out of the start");
+        assert_part!(parse, part1, "S-7.\n|.|-\nL-J.", 4);
+    }
+
+    #[test]
+    fn test_start_pipe() {
+        let start_pipe = |input| {
+            let input = finish(input, parse(input)).unwrap();
+            start_pipe(&input.main_loop().unwrap())
+        };
+        assert_eq!(start_pipe(INPUT_1), Some(Pipe::SouthToEast));
+        assert_eq!(start_pipe(INPUT_2), Some(Pipe::SouthToEast));
+        assert_eq!(start_pipe(INPUT_4), Some(Pipe::SouthToWest));
+        assert_eq!(
+            start_pipe(".....\n.F-7.\n.|.|.\n.L-S.\n....."),
+            Some(Pipe::NorthToWest)
+        );
+        // Pipes that lead into the start from outside the loop do not count
+        assert_eq!(start_pipe("-S-7\n.|.|\n.L-J"), Some(Pipe::SouthToEast));
+    }
+}
@@ -0,0 +1,211 @@
+use itertools::Itertools;
+use utils::grid::Grid;
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Observation {
+    Empty,
+    Galaxy,
+}
+
+impl Observation {
+    const fn symbol(self) -> char {
+        match self {
+            Self::Empty => '.',
+            Self::Galaxy => '#',
+        }
+    }
+
+    const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            '#' => Some(Self::Galaxy),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData(Grid<Observation>);
+
+impl ToPuzzleText for InputData {
+    fn to_puzzle_text(&self) -> String {
+        self.0.render(|observation| observation.symbol())
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::combinator::map;
+    let mut parser = map(
+        Grid::parser("observation", Observation::from_char),
+        InputData,
+    );
+    parser(input)
+}
+
+const fn manhattan((r_a, c_a): (usize, usize), (r_b, c_b): (usize, usize)) -> usize {
+    r_a.abs_diff(r_b) + c_a.abs_diff(c_b)
+}
+
+struct Offsets {
+    row: Vec<usize>,
+    col: Vec<usize>,
+}
+
+impl Offsets {
+    fn new(scale: usize, rows: &[usize], cols: &[usize]) -> Self {
+        Self {
+            row: (0..=rows.iter().copied().max().unwrap_or_default())
+                .scan(0, |acc, cur| {
+                    if !rows.contains(&cur) {
+                        *acc += scale - 1;
+                    }
+                    Some(*acc)
+                })
+                .collect(),
+            col: (0..=cols.iter().copied().max().unwrap_or_default())
+                .scan(0, |acc, cur| {
+                    if !cols.contains(&cur) {
+                        *acc += scale - 1;
+                    }
+                    Some(*acc)
+                })
+                .collect(),
+        }
+    }
+
+    fn resize(&self, (row, col): (usize, usize)) -> (usize, usize) {
+        (self.row[row] + row, self.col[col] + col)
+    }
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part1(input: &InputData) -> AocResult<usize> {
+    let galaxies: Vec<(usize, usize)> = input
+        .0
+        .indexed_iter()
+        .filter(|(_, &o)| o == Observation::Galaxy)
+        .map(|(pos, _)| pos)
+        .collect();
+    let rows: Vec<usize> = galaxies.iter().map(|&(row, _)| row).collect();
+    let cols: Vec<usize> = galaxies.iter().map(|&(_, col)| col).collect();
+    let offsets: Offsets = Offsets::new(2, &rows, &cols);
+    Ok(galaxies
+        .iter()
+        .map(|&pos| offsets.resize(pos))
+        .combinations(2)
+        .map(|v| (v[0], v[1]))
+        .map(|(a, b)| manhattan(a, b))
+        .sum())
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part2(input: &InputData) -> AocResult<usize> {
+    let galaxies: Vec<(usize, usize)> = input
+        .0
+        .indexed_iter()
+        .filter(|(_, &o)| o == Observation::Galaxy)
+        .map(|(pos, _)| pos)
+        .collect();
+    let rows: Vec<usize> = galaxies.iter().map(|&(row, _)| row).collect();
+    let cols: Vec<usize> = galaxies.iter().map(|&(_, col)| col).collect();
+    let offsets: Offsets = Offsets::new(1_000_000, &rows, &cols);
+    Ok(galaxies
+        .iter()
+        .map(|&pos| offsets.resize(pos))
+        .combinations(2)
+        .map(|v| (v[0], v[1]))
+        .map(|(a, b)| manhattan(a, b))
+        .sum())
+}
+
+const INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const EXAMPLES: &'static [&'static str] = &[INPUT];
+
+    type Input = InputData;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        use Observation::*;
+
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData(Grid::from([
+                [Empty, Empty, Empty, Galaxy, Empty, Empty, Empty, Empty, Empty, Empty],
+                [Empty, Empty, Empty, Empty, Empty, Empty, Empty, Galaxy, Empty, Empty],
+                [Galaxy, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty],
+                [Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty],
+                [Empty, Empty, Empty, Empty, Empty, Empty, Galaxy, Empty, Empty, Empty],
+                [Empty, Galaxy, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty],
+                [Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Galaxy],
+                [Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty],
+                [Empty, Empty, Empty, Empty, Empty, Empty, Empty, Galaxy, Empty, Empty],
+                [Galaxy, Empty, Empty, Empty, Galaxy, Empty, Empty, Empty, Empty, Empty]
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip!(parse, INPUT);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 374);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 82000210);
+    }
+
+    #[test]
+    fn test_invalid_observation() {
+        let input = "#..\n.x.\n";
+        assert_eq!(
+            finish(input, parse(input)).unwrap_err().to_string(),
+            "line 2, column 2: expected observation\n  |\n2 | .x.\n  |  ^"
+        );
+    }
+
+    #[test]
+    fn test_far_galaxies() {
+        assert_part!(parse, part1, ".....\n....#\n.....\n....#", 3);
+    }
+}
@@ -0,0 +1,595 @@
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Condition {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl Condition {
+    const fn symbol(self) -> char {
+        match self {
+            Self::Operational => '.',
+            Self::Damaged => '#',
+            Self::Unknown => '?',
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+struct Report {
+    conditions: Vec<Condition>,
+    checks: Vec<u64>,
+}
+
+impl Report {
+    /// The report copied `folds` times, with unknown springs in between
+    const fn unfold(&self, folds: usize) -> Unfolded<'_> {
+        Unfolded {
+            report: self,
+            folds,
+        }
+    }
+}
+
+/// Unfolded report that reads the springs of the folded one by index
+#[derive(Debug, Clone, Copy)]
+struct Unfolded<'a> {
+    report: &'a Report,
+    folds: usize,
+}
+
+impl<'a> Unfolded<'a> {
+    fn len(&self) -> usize {
+        ((self.report.conditions.len() + 1) * self.folds).saturating_sub(1)
+    }
+
+    fn condition(&self, i: usize) -> Condition {
+        let conditions = &self.report.conditions;
+        conditions
+            .get(i % (conditions.len() + 1))
+            .copied()
+            .unwrap_or(Condition::Unknown)
+    }
+
+    fn checks(&self) -> usize {
+        self.report.checks.len() * self.folds
+    }
+
+    fn check(&self, j: usize) -> usize {
+        let checks = &self.report.checks;
+        usize::try_from(checks[j % checks.len()]).unwrap_or(usize::MAX)
+    }
+
+    /// Springs after a block of damaged ones for check `j` that starts at `i`
+    ///
+    /// The block needs `run` springs from `i` on that can be damaged, and
+    /// a spring after it that can be operational, which is skipped too.
+    fn block_end(&self, i: usize, j: usize, run: usize) -> Option<usize> {
+        let len = self.len();
+        let check = self.check(j);
+        let end = i.checked_add(check)?;
+        let fits = check > 0 && check <= run && end <= len;
+        (fits && (end == len || self.condition(end) != Condition::Damaged))
+            .then_some((end + 1).min(len))
+    }
+
+    /// Table of the arrangements that match the checks
+    ///
+    /// Entry `i * (checks + 1) + j` counts those of the springs from `i`
+    /// on and the checks from `j` on. The table is filled from the end of
+    /// the springs, so every count it needs is already there.
+    fn counts(&self) -> Vec<usize> {
+        let (len, checks) = (self.len(), self.checks());
+        let width = checks + 1;
+        let mut counts = vec![0; (len + 1) * width];
+        counts[len * width + checks] = 1;
+        // Springs from `i` on that can all be damaged
+        let mut damaged_run = 0;
+        for i in (0..len).rev() {
+            let condition = self.condition(i);
+            damaged_run = match condition {
+                Condition::Operational => 0,
+                _ => damaged_run + 1,
+            };
+            for j in 0..=checks {
+                let mut count = 0;
+                if condition != Condition::Damaged {
+                    count += counts[(i + 1) * width + j];
+                }
+                if condition != Condition::Operational && j < checks {
+                    if let Some(next) = self.block_end(i, j, damaged_run) {
+                        count += counts[next * width + j + 1];
+                    }
+                }
+                counts[i * width + j] = count;
+            }
+        }
+        counts
+    }
+
+    /// Number of arrangements that match the checks
+    fn count(&self) -> usize {
+        self.counts()[0]
+    }
+
+    /// Iterator over the first `limit` arrangements that match the checks
+    ///
+    /// Every unknown spring is replaced by an operational or a damaged one.
+    /// The arrangements come in the order of their text, with `#` before `.`.
+    fn arrangements(&self, limit: usize) -> Arrangements<'a> {
+        Arrangements {
+            unfolded: *self,
+            counts: self.counts(),
+            stack: vec![(0, 0, Vec::new())],
+            remaining: limit,
+        }
+    }
+}
+
+/// Arrangements of an unfolded report, see [`Unfolded::arrangements`]
+///
+/// The table of counts steers the search, so it never tries springs that
+/// lead to no arrangement at all.
+#[derive(Debug)]
+struct Arrangements<'a> {
+    unfolded: Unfolded<'a>,
+    counts: Vec<usize>,
+    /// Spring and check to go on from, with the springs chosen before
+    stack: Vec<(usize, usize, Vec<Condition>)>,
+    remaining: usize,
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Vec<Condition>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let unfolded = self.unfolded;
+        let (len, width) = (unfolded.len(), unfolded.checks() + 1);
+        while self.remaining > 0 {
+            let (i, j, mut springs) = self.stack.pop()?;
+            if self.counts[i * width + j] == 0 {
+                continue;
+            }
+            if i == len {
+                self.remaining -= 1;
+                return Some(springs);
+            }
+            let condition = unfolded.condition(i);
+            if condition != Condition::Damaged {
+                let mut springs = springs.clone();
+                springs.push(Condition::Operational);
+                self.stack.push((i + 1, j, springs));
+            }
+            if condition != Condition::Operational && j < unfolded.checks() {
+                let run = (i..len)
+                    .take_while(|&k| unfolded.condition(k) != Condition::Operational)
+                    .count();
+                if let Some(next) = unfolded.block_end(i, j, run) {
+                    springs.resize(i + unfolded.check(j), Condition::Damaged);
+                    springs.resize(next, Condition::Operational);
+                    self.stack.push((next, j + 1, springs));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData(Vec<Report>);
+
+impl InputData {
+    /// Total of the arrangements of every report unfolded `folds` times
+    ///
+    /// # Errors
+    ///
+    /// Fails when `folds` is 0, as there is no report left to arrange
+    pub fn arrangement_count(&self, folds: usize) -> AocResult<usize> {
+        check_folds(folds)?;
+        Ok(self
+            .0
+            .iter()
+            .map(|report| report.unfold(folds).count())
+            .sum())
+    }
+
+    /// Text of the first `limit` arrangements of report `index` unfolded
+    /// `folds` times
+    ///
+    /// # Errors
+    ///
+    /// Fails when there is no such report or `folds` is 0
+    pub fn arrangements(
+        &self,
+        index: usize,
+        folds: usize,
+        limit: usize,
+    ) -> AocResult<impl Iterator<Item = String> + '_> {
+        check_folds(folds)?;
+        let report = self
+            .0
+            .get(index)
+            .with_context(|| format!("No report {index}, there are {}", self.0.len()))?;
+        Ok(report
+            .unfold(folds)
+            .arrangements(limit)
+            .map(|springs| springs.into_iter().map(Condition::symbol).collect()))
+    }
+}
+
+fn check_folds(folds: usize) -> AocResult<()> {
+    if folds == 0 {
+        bail!("Reports must be unfolded at least once");
+    }
+    Ok(())
+}
+
+impl ToPuzzleText for InputData {
+    fn to_puzzle_text(&self) -> String {
+        let reports: Vec<String> = self
+            .0
+            .iter()
+            .map(|Report { conditions, checks }| {
+                let conditions: String = conditions.iter().map(|c| c.symbol()).collect();
+                let checks: Vec<String> = checks.iter().map(u64::to_string).collect();
+                format!("{conditions} {}", checks.join(","))
+            })
+            .collect();
+        reports.join("\n")
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{line_ending, space1, u64},
+        combinator::map,
+        error::context,
+        multi::{many1, separated_list1},
+        sequence::separated_pair,
+    };
+    let operational = map(tag("."), |_| Condition::Operational);
+    let damaged = map(tag("#"), |_| Condition::Damaged);
+    let unknown = map(tag("?"), |_| Condition::Unknown);
+    let conditions = many1(alt((operational, damaged, unknown)));
+    let check = separated_list1(tag(","), u64);
+    let report = context(
+        "report",
+        map(
+            separated_pair(conditions, space1, check),
+            |(conditions, checks)| Report { conditions, checks },
+        ),
+    );
+    let mut parser = map(separated_list1(line_ending, report), InputData);
+    parser(input)
+}
+
+fn part1(input: &InputData) -> AocResult<usize> {
+    input.arrangement_count(1)
+}
+
+fn part2(input: &InputData) -> AocResult<usize> {
+    input.arrangement_count(5)
+}
+
+const INPUT: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const EXAMPLES: &'static [&'static str] = &[INPUT];
+
+    type Input = InputData;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE_1: &str = "?.#.### 1,1,3";
+
+    #[test]
+    fn test_line1() {
+        assert_part!(parse, part1, LINE_1, 1);
+    }
+
+    #[test]
+    fn test_parser() {
+        use Condition::*;
+
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData(vec![
+                Report {
+                    conditions: vec![
+                        Unknown,
+                        Unknown,
+                        Unknown,
+                        Operational,
+                        Damaged,
+                        Damaged,
+                        Damaged
+                    ],
+                    checks: vec![1, 1, 3]
+                },
+                Report {
+                    conditions: vec![
+                        Operational,
+                        Unknown,
+                        Unknown,
+                        Operational,
+                        Operational,
+                        Unknown,
+                        Unknown,
+                        Operational,
+                        Operational,
+                        Operational,
+                        Unknown,
+                        Damaged,
+                        Damaged,
+                        Operational
+                    ],
+                    checks: vec![1, 1, 3]
+                },
+                Report {
+                    conditions: vec![
+                        Unknown, Damaged, Unknown, Damaged, Unknown, Damaged, Unknown, Damaged,
+                        Unknown, Damaged, Unknown, Damaged, Unknown, Damaged, Unknown
+                    ],
+                    checks: vec![1, 3, 1, 6]
+                },
+                Report {
+                    conditions: vec![
+                        Unknown,
+                        Unknown,
+                        Unknown,
+                        Unknown,
+                        Operational,
+                        Damaged,
+                        Operational,
+                        Operational,
+                        Operational,
+                        Damaged,
+                        Operational,
+                        Operational,
+                        Operational
+                    ],
+                    checks: vec![4, 1, 1]
+                },
+                Report {
+                    conditions: vec![
+                        Unknown,
+                        Unknown,
+                        Unknown,
+                        Unknown,
+                        Operational,
+                        Damaged,
+                        Damaged,
+                        Damaged,
+                        Damaged,
+                        Damaged,
+                        Damaged,
+                        Operational,
+                        Operational,
+                        Damaged,
+                        Damaged,
+                        Damaged,
+                        Damaged,
+                        Damaged,
+                        Operational
+                    ],
+                    checks: vec![1, 6, 5]
+                },
+                Report {
+                    conditions: vec![
+                        Unknown, Damaged, Damaged, Damaged, Unknown, Unknown, Unknown, Unknown,
+                        Unknown, Unknown, Unknown, Unknown
+                    ],
+                    checks: vec![3, 2, 1]
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip!(parse, INPUT);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 21);
+        // Groups longer than the row never fit
+        assert_part!(parse, part1, "### 4", 0);
+        assert_part!(parse, part1, "??? 5", 0);
+        assert_part!(parse, part1, "?? 3", 0);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 525152);
+        assert_part!(parse, part2, "### 4", 0);
+    }
+
+    /// Counts arrangements by trying every combination of unknown springs
+    #[allow(clippy::unnecessary_wraps)]
+    fn brute_force(input: &InputData) -> AocResult<usize> {
+        Ok(input
+            .0
+            .iter()
+            .map(|report| {
+                let unknowns: Vec<usize> = (0..report.conditions.len())
+                    .filter(|&i| report.conditions[i] == Condition::Unknown)
+                    .collect();
+                (0..1_u32 << unknowns.len())
+                    .filter(|mask| {
+                        let mut conditions = report.conditions.clone();
+                        for (bit, &i) in unknowns.iter().enumerate() {
+                            conditions[i] = if mask & (1 << bit) == 0 {
+                                Condition::Operational
+                            } else {
+                                Condition::Damaged
+                            };
+                        }
+                        let groups: Vec<u64> = conditions
+                            .split(|c| *c == Condition::Operational)
+                            .filter(|group| !group.is_empty())
+                            .map(|group| group.len() as u64)
+                            .collect();
+                        groups == report.checks
+                    })
+                    .count()
+            })
+            .sum())
+    }
+
+    #[test]
+    fn test_part1_brute_force() {
+        assert_equivalent!(parse, part1, brute_force, |rng| {
+            (0..5)
+                .map(|_| random::springs_report(rng, 12))
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+    }
+
+    /// Unfolds the reports into new ones before counting
+    fn copy_unfolded(input: &InputData, folds: usize) -> usize {
+        input
+            .0
+            .iter()
+            .map(|report| {
+                let mut conditions = Vec::new();
+                let mut checks = Vec::new();
+                for fold in 0..folds {
+                    if fold > 0 {
+                        conditions.push(Condition::Unknown);
+                    }
+                    conditions.extend(&report.conditions);
+                    checks.extend(&report.checks);
+                }
+                Report { conditions, checks }.unfold(1).count()
+            })
+            .sum()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn part2_unfolded(input: &InputData) -> AocResult<usize> {
+        Ok(copy_unfolded(input, 5))
+    }
+
+    #[test]
+    fn test_part2_unfolded() {
+        assert_equivalent!(parse, part2, part2_unfolded, |rng| {
+            (0..5)
+                .map(|_| random::springs_report(rng, 8))
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+    }
+
+    #[test]
+    fn test_unfold_factor() {
+        let input = finish(INPUT, parse(INPUT)).unwrap();
+        assert_eq!(
+            input.arrangement_count(0).unwrap_err().to_string(),
+            "Reports must be unfolded at least once"
+        );
+        assert_eq!(input.arrangement_count(1).unwrap(), 21);
+        assert_eq!(input.arrangement_count(5).unwrap(), 525_152);
+        let mut rng = random::Rng::new(25);
+        for _ in 0..20 {
+            let input = random::springs_report(&mut rng, 8);
+            let input = finish(&input, parse(&input)).unwrap();
+            for folds in 1..4 {
+                assert_eq!(
+                    input.arrangement_count(folds).unwrap(),
+                    copy_unfolded(&input, folds)
+                );
+            }
+        }
+    }
+
+    fn arrangements(line: &str, folds: usize, limit: usize) -> Vec<String> {
+        let input = finish(line, parse(line)).unwrap();
+        input.arrangements(0, folds, limit).unwrap().collect()
+    }
+
+    #[test]
+    fn test_arrangements() {
+        assert_eq!(arrangements("???.### 1,1,3", 1, 10), ["#.#.###"]);
+        assert_eq!(
+            arrangements(".??..??...?##. 1,1,3", 1, 10),
+            [
+                ".#...#....###.",
+                ".#....#...###.",
+                "..#..#....###.",
+                "..#...#...###."
+            ]
+        );
+        assert_eq!(arrangements(".??..??...?##. 1,1,3", 1, 3).len(), 3);
+        assert_eq!(arrangements("???.### 1,1,3", 2, 10), ["#.#.###.#.#.###"]);
+        assert!(arrangements("#.# 1", 1, 10).is_empty());
+        for line in ["### 4", "??? 5", "?? 3", "#.# 3"] {
+            assert!(arrangements(line, 1, 10).is_empty(), "{line}");
+            assert!(arrangements(line, 2, 10).is_empty(), "{line}");
+            let input = finish(line, parse(line)).unwrap();
+            assert_eq!(input.arrangement_count(1).unwrap(), 0, "{line}");
+        }
+        let input = finish("#.# 1", parse("#.# 1")).unwrap();
+        assert_eq!(
+            input.arrangements(1, 1, 10).err().unwrap().to_string(),
+            "No report 1, there are 1"
+        );
+        assert!(input.arrangements(0, 0, 10).is_err());
+
+        let mut rng = random::Rng::new(12);
+        for _ in 0..50 {
+            let line = random::springs_report(&mut rng, 10);
+            let input = finish(&line, parse(&line)).unwrap();
+            let report = &input.0[0];
+            let all: Vec<Vec<Condition>> = report.unfold(1).arrangements(usize::MAX).collect();
+            assert_eq!(all.len(), report.unfold(1).count(), "{line}");
+            for springs in &all {
+                let groups: Vec<u64> = springs
+                    .split(|c| *c == Condition::Operational)
+                    .filter(|group| !group.is_empty())
+                    .map(|group| group.len() as u64)
+                    .collect();
+                assert_eq!(groups, report.checks, "{line}");
+                assert!(springs
+                    .iter()
+                    .zip(&report.conditions)
+                    .all(|(spring, condition)| condition == &Condition::Unknown
+                        || spring == condition));
+            }
+            assert!(all.windows(2).all(|pair| pair[0] != pair[1]), "{line}");
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::{anyhow, Context};
+use std::path::Path;
+
+use crate::{AocResult, SolutionPart};
+
+/// Answers accepted by Advent of Code, read from a day's `answers.toml`
+///
+/// ```toml
+/// part1 = 142
+/// part2 = "281"
+/// ```
+///
+/// Either part may be left out until it has been solved.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl ExpectedAnswers {
+    /// Reads the expected answers from a toml file
+    ///
+    /// # Errors
+    ///
+    /// Fails when the file cannot be read or is not valid toml
+    pub fn load(path: &Path) -> AocResult<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Unable to read answers from {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid answers in {}", path.display()))
+    }
+
+    /// Parses the expected answers from toml
+    ///
+    /// # Errors
+    ///
+    /// Fails when the content is not valid toml or an answer is not a
+    /// string or integer
+    pub fn parse(content: &str) -> AocResult<Self> {
+        let table: toml::Table = content.parse()?;
+        let answer = |key: &str| match table.get(key) {
+            None => Ok(None),
+            Some(toml::Value::String(s)) => Ok(Some(s.clone())),
+            Some(toml::Value::Integer(i)) => Ok(Some(i.to_string())),
+            Some(value) => Err(anyhow!("{key} must be a string or integer, found {value}")),
+        };
+        Ok(Self {
+            part1: answer("part1")?,
+            part2: answer("part2")?,
+        })
+    }
+
+    /// Recorded answer for a single part
+    #[must_use]
+    pub fn get(&self, part: SolutionPart) -> Option<&str> {
+        match part {
+            SolutionPart::PartOne => self.part1.as_deref(),
+            SolutionPart::PartTwo => self.part2.as_deref(),
+            SolutionPart::Both => None,
+        }
+    }
+
+    /// Compares an answer with the recorded one
+    ///
+    /// Returns [`None`] when nothing is recorded for the part.
+    #[must_use]
+    pub fn verify(&self, part: SolutionPart, answer: &str) -> Option<AocResult<()>> {
+        let expected = self.get(part)?;
+        let answer = answer.trim_matches('"');
+        if answer == expected {
+            Some(Ok(()))
+        } else {
+            let n = part.number().unwrap_or_default();
+            Some(Err(anyhow!(
+                "Part {n} answer {answer} does not match the recorded answer {expected}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            ExpectedAnswers::parse("part1 = 142\npart2 = \"281\"").unwrap(),
+            ExpectedAnswers {
+                part1: Some(String::from("142")),
+                part2: Some(String::from("281"))
+            }
+        );
+        assert_eq!(
+            ExpectedAnswers::parse("part1 = 142").unwrap(),
+            ExpectedAnswers {
+                part1: Some(String::from("142")),
+                part2: None
+            }
+        );
+        assert!(ExpectedAnswers::parse("part1 = 1.5").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let expected = ExpectedAnswers::parse("part1 = 142").unwrap();
+        assert!(matches!(
+            expected.verify(SolutionPart::PartOne, "142"),
+            Some(Ok(()))
+        ));
+        assert!(matches!(
+            expected.verify(SolutionPart::PartOne, "143"),
+            Some(Err(_))
+        ));
+        assert!(expected.verify(SolutionPart::PartTwo, "281").is_none());
+    }
+}
@@ -0,0 +1,358 @@
+//! Rectangular grids of puzzle tiles
+//!
+//! Positions are `(row, column)` pairs counted from the top left, the same
+//! order `ndarray` indexes in. Moving around goes through
+//! [`Grid::offset`], which gives `None` instead of leaving the grid.
+
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use nom::combinator::map;
+
+use crate::{parsers, ParseResult};
+
+/// Position of a tile, as `(row, column)`
+pub type Position = (usize, usize);
+
+/// Offsets of the tiles above, right of, below and left of a tile
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets of the tiles around a tile, clockwise from the top left
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+/// Tiles stored row after row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of rows that all have the same, non-zero length
+    #[must_use]
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first()?.len();
+        if width == 0 || rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Grid of `height` rows and `width` columns, with the tile of each position
+    #[must_use]
+    pub fn from_fn((height, width): (usize, usize), mut tile: impl FnMut(Position) -> T) -> Self {
+        Self {
+            width,
+            cells: (0..height * width)
+                .map(|i| tile((i / width, i % width)))
+                .collect(),
+        }
+    }
+
+    /// Parser of lines of characters, with `cell` turning each into a tile
+    ///
+    /// See [`parsers::rows`] for the errors, which `name` the tiles.
+    pub fn parser<'a>(
+        name: &'static str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> impl FnMut(&'a str) -> ParseResult<'a, Self> {
+        map(parsers::rows(name, cell), |rows| Self {
+            width: rows[0].len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or_default()
+    }
+
+    /// Number of rows and columns
+    #[must_use]
+    pub fn dim(&self) -> (usize, usize) {
+        (self.height(), self.width)
+    }
+
+    #[must_use]
+    pub fn get(&self, (row, col): Position) -> Option<&T> {
+        if col < self.width {
+            self.cells.get(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, (row, col): Position) -> Option<&mut T> {
+        if col < self.width {
+            self.cells.get_mut(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    /// Position `(rows, columns)` away from `position`, if it is on the grid
+    #[must_use]
+    pub fn offset(&self, (row, col): Position, (rows, cols): (isize, isize)) -> Option<Position> {
+        let position = (row.checked_add_signed(rows)?, col.checked_add_signed(cols)?);
+        self.get(position).map(|_| position)
+    }
+
+    /// Positions of the tiles above, right of, below and left of `position`
+    pub fn neighbours_4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// Positions of the tiles around `position`, diagonals included
+    pub fn neighbours_8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Tiles with their positions, row after row
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, tile)| ((i / width, i % width), tile))
+    }
+
+    /// Position of the first tile, row after row, that matches `predicate`
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.indexed_iter()
+            .find(|(_, tile)| predicate(tile))
+            .map(|(position, _)| position)
+    }
+
+    /// Tiles of a row, from left to right
+    #[must_use]
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        self.cells.chunks_exact(self.width.max(1)).nth(row)
+    }
+
+    /// Tiles of a column, from top to bottom
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let tiles = if col < self.width {
+            &self.cells[col..]
+        } else {
+            &[]
+        };
+        tiles.iter().step_by(self.width.max(1))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Grid of the same shape with `f` applied to every tile
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Text of the grid, with `symbol` drawing each tile as a character
+    #[must_use]
+    pub fn render(&self, symbol: impl Fn(&T) -> char) -> String {
+        let rows: Vec<String> = self
+            .rows()
+            .map(|row| row.iter().map(&symbol).collect())
+            .collect();
+        rows.join("\n")
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Grid of `height` rows and `width` columns of the same tile
+    #[must_use]
+    pub fn filled(dim: (usize, usize), tile: T) -> Self {
+        Self::from_fn(dim, |_| tile.clone())
+    }
+
+    /// Grid with rows and columns swapped
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let (height, width) = self.dim();
+        Self::from_fn((width, height), |(row, col)| self[(col, row)].clone())
+    }
+
+    /// Grid turned a quarter clockwise, so the first column becomes the first row
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self {
+        let (height, width) = self.dim();
+        Self::from_fn((width, height), |(row, col)| {
+            self[(height - 1 - col, row)].clone()
+        })
+    }
+
+    /// Grid turned a quarter anticlockwise, so the last column becomes the first row
+    #[must_use]
+    pub fn rotate_anticlockwise(&self) -> Self {
+        let (height, width) = self.dim();
+        Self::from_fn((width, height), |(row, col)| {
+            self[(col, width - 1 - row)].clone()
+        })
+    }
+}
+
+impl<T, const W: usize, const H: usize> From<[[T; W]; H]> for Grid<T> {
+    fn from(rows: [[T; W]; H]) -> Self {
+        Self {
+            width: W,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{position:?} is not on a grid of {:?}", self.dim()))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let dim = self.dim();
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} is not on a grid of {dim:?}"))
+    }
+}
+
+/// Lines of tiles, padded to the widest so the columns line up
+///
+/// Tiles wider than a character are separated by a space.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tiles: Vec<String> = self.cells.iter().map(ToString::to_string).collect();
+        let widest = tiles.iter().map(|t| t.chars().count()).max().unwrap_or(0);
+        let separator = if widest > 1 { " " } else { "" };
+        for (i, row) in tiles.chunks(self.width.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for (j, tile) in row.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(separator)?;
+                }
+                write!(f, "{tile:>widest$}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finish;
+
+    fn digits() -> Grid<u32> {
+        Grid::from([[1, 2, 3], [4, 5, 6]])
+    }
+
+    #[test]
+    fn test_parser() {
+        let input = "123\n456\n";
+        let grid = finish(input, Grid::parser("digit", |c| c.to_digit(10))(input)).unwrap();
+        assert_eq!(grid, digits());
+        assert_eq!(grid.dim(), (2, 3));
+        assert_eq!(
+            grid.render(|d| char::from_digit(*d, 10).unwrap()),
+            "123\n456"
+        );
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap()[(1, 0)],
+            3
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits();
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (0, 3)), None);
+        assert_eq!(grid.offset((0, 2), (1, -2)), Some((1, 0)));
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(
+            grid.neighbours_4((0, 1)).collect::<Vec<_>>(),
+            [(0, 2), (1, 1), (0, 0)]
+        );
+        assert_eq!(
+            grid.neighbours_8((1, 2)).collect::<Vec<_>>(),
+            [(0, 1), (0, 2), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = digits();
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.position(|&d| d > 4), Some((1, 1)));
+        assert_eq!(grid.indexed_iter().nth(4), Some(((1, 1), &5)));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = digits();
+        assert_eq!(grid.transpose(), Grid::from([[1, 4], [2, 5], [3, 6]]));
+        assert_eq!(
+            grid.rotate_clockwise(),
+            Grid::from([[4, 1], [5, 2], [6, 3]])
+        );
+        assert_eq!(
+            grid.rotate_anticlockwise(),
+            Grid::from([[3, 6], [2, 5], [1, 4]])
+        );
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+        assert_eq!(grid.map(|d| d * 2)[(1, 2)], 12);
+        assert_eq!(Grid::filled((2, 2), '.').to_string(), "..\n..");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(digits().to_string(), "123\n456");
+        let grid = Grid::from([[1, 20], [300, 4]]);
+        assert_eq!(grid.to_string(), "  1  20\n300   4");
+    }
+}
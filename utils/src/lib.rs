@@ -1,40 +1,261 @@
-pub use anyhow::Result as AocResult;
+mod answers;
+pub mod grid;
+mod output;
+mod parse_error;
+pub mod parsers;
+mod puzzle_text;
+pub mod random;
+mod solution;
+
+pub use answers::ExpectedAnswers;
+pub use anyhow::{anyhow, bail, Context, Result as AocResult};
 pub use clap::Parser;
 use clap::{builder::PossibleValue, ValueEnum};
-use std::path::PathBuf;
+pub use output::{day_from_package, JsonRecord, OutputFormat};
+pub use parse_error::{finish, finish_lenient, ParseError};
+pub use puzzle_text::{round_trip, RoundTrip, ToPuzzleText};
+pub use solution::{run, Answers, DynSolution, PartAnswer, Registry, Solution};
+use std::{
+    cell::OnceCell,
+    fmt::Debug,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
-pub type ParseResult<'a, T> = nom::IResult<&'a str, T>;
+/// Result of every day's parser
+///
+/// Errors keep the context of each combinator, so [`finish`] can tell
+/// where and why the input did not parse.
+pub type ParseResult<'a, T> = nom::IResult<&'a str, T, nom::error::VerboseError<&'a str>>;
 
 #[derive(Debug, Parser)]
 #[command(author, about, long_about = None)]
 pub struct Cli {
-    /// Input file
-    input_file: PathBuf,
+    /// Input file, or `-` to read standard input
+    #[arg(required_unless_present_any = ["example", "input_str"])]
+    input_file: Option<PathBuf>,
+
+    /// Run on the N-th example of the puzzle description instead, counted from 1
+    #[arg(short, long, value_name = "N", conflicts_with_all = ["input_file", "input_str"])]
+    example: Option<usize>,
+
+    /// Run on this text instead of an input file
+    #[arg(long, value_name = "TEXT", conflicts_with = "input_file")]
+    input_str: Option<String>,
 
     /// Part of the task to do
     #[arg(short, long, value_enum, default_value_t)]
     part: SolutionPart,
+
+    /// Report how long parsing and each part took
+    #[arg(short, long)]
+    time: bool,
+
+    /// How to print the results
+    #[arg(short, long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Compare the answers with the recorded ones and fail on a mismatch
+    #[arg(short, long)]
+    check: bool,
+
+    /// Recorded answers to check against [default: answers.toml next to the input file]
+    #[arg(long, requires = "check")]
+    answers: Option<PathBuf>,
+
+    /// Ignore input the parser left over instead of failing
+    #[arg(long)]
+    lenient: bool,
+
+    /// Examples of the day that `--example` picks from
+    #[arg(skip)]
+    examples: &'static [&'static str],
+
+    /// Recorded answers, read the first time a part is checked
+    #[arg(skip)]
+    expected: OnceCell<ExpectedAnswers>,
 }
 
 impl Cli {
-    /// Reads input file and returns its content
+    /// Sets the examples that `--example` picks from
+    #[must_use]
+    pub const fn with_examples(mut self, examples: &'static [&'static str]) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    /// Checks the flag combinations clap cannot express
     ///
     /// # Errors
     ///
-    /// Check errors for [`std::fs::read_to_string`]
+    /// Fails when `--check` has no answers file to check against
+    pub fn validate(&self) -> AocResult<()> {
+        if self.check {
+            self.answers_path()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the puzzle input to run on
+    ///
+    /// It is read from the input file or standard input, or taken from
+    /// `--input-str` or the examples.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the input cannot be read or the example does not exist
     pub fn input(&self) -> AocResult<String> {
-        Ok(std::fs::read_to_string(&self.input_file)?)
+        if let Some(text) = &self.input_str {
+            return Ok(text.clone());
+        }
+        if let Some(n) = self.example {
+            return n
+                .checked_sub(1)
+                .and_then(|i| self.examples.get(i))
+                .map(ToString::to_string)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("No example {n}, there are {}", self.examples.len())
+                });
+        }
+        match self.input_file.as_deref() {
+            Some(path) if path != Path::new("-") => Ok(std::fs::read_to_string(path)?),
+            _ => Ok(std::io::read_to_string(std::io::stdin())?),
+        }
+    }
+
+    /// Finishes the parse of `input`, failing on leftovers unless lenient
+    ///
+    /// # Errors
+    ///
+    /// Returns where in `input` the parse failed or stopped
+    pub fn finish<T>(&self, input: &str, parsed: ParseResult<'_, T>) -> AocResult<T> {
+        if self.lenient {
+            finish_lenient(input, parsed)
+        } else {
+            finish(input, parsed)
+        }
     }
 
     /// Determines if the part should run based on cli flags
     #[must_use]
     pub fn should_run(&self, part: SolutionPart) -> bool {
-        if self.part == SolutionPart::Both {
-            true
-        } else {
-            self.part == part
+        self.part.includes(part)
+    }
+
+    /// File of the recorded answers, `--answers` or the one next to the input file
+    ///
+    /// The answers of an input file are no good for an example, `--input-str`
+    /// or standard input, so those need `--answers`.
+    fn answers_path(&self) -> AocResult<PathBuf> {
+        match (&self.answers, self.input_file.as_deref()) {
+            (Some(path), _) => Ok(path.clone()),
+            (None, Some(path)) if path != Path::new("-") => Ok(path.with_file_name("answers.toml")),
+            (None, _) => bail!("--check needs --answers when the input is not a file"),
+        }
+    }
+
+    /// Reads the recorded answers to check against, once for all parts
+    ///
+    /// # Errors
+    ///
+    /// Fails when there is no answers file for the input, or it cannot be
+    /// read or parsed
+    pub fn expected_answers(&self) -> AocResult<&ExpectedAnswers> {
+        if let Some(expected) = self.expected.get() {
+            return Ok(expected);
+        }
+        let expected = ExpectedAnswers::load(&self.answers_path()?)?;
+        Ok(self.expected.get_or_init(|| expected))
+    }
+
+    /// Prints how long a phase took when timing was requested
+    pub fn report_time(&self, phase: &str, elapsed: Duration) {
+        if self.time && self.format == OutputFormat::Text {
+            println!("{phase} took {elapsed:.2?}");
+        }
+    }
+
+    /// Reports the outcome of parsing the input for `part`
+    ///
+    /// # Errors
+    ///
+    /// Passes through the parse error after reporting it
+    pub fn report_parse<T>(
+        &self,
+        day: Option<u8>,
+        part: SolutionPart,
+        parsed: AocResult<T>,
+        elapsed: Duration,
+    ) -> AocResult<T> {
+        match (&parsed, self.format) {
+            (Ok(_), OutputFormat::Text) => match part.number() {
+                Some(n) => self.report_time(&format!("Parse {n}"), elapsed),
+                None => self.report_time("Parse", elapsed),
+            },
+            (Err(err), OutputFormat::Json) => {
+                let mut record = JsonRecord::new(day, part.number(), elapsed);
+                record.error = Some(format!("{err:#}"));
+                record.print();
+            }
+            _ => {}
         }
+        parsed
     }
+
+    /// Reports the answer for `part`
+    ///
+    /// # Errors
+    ///
+    /// Passes through the error of the part after reporting it
+    pub fn report_part<T: Debug>(
+        &self,
+        day: Option<u8>,
+        part: SolutionPart,
+        answer: AocResult<T>,
+        elapsed: Duration,
+    ) -> AocResult<()> {
+        let n = part.number().unwrap_or_default();
+        // Failing to read the answers is reported after the answer is shown
+        let verdict = match &answer {
+            Ok(answer) if self.check => self
+                .expected_answers()
+                .map(|expected| expected.verify(part, &format!("{answer:?}"))),
+            _ => Ok(None),
+        };
+        match self.format {
+            OutputFormat::Text => {
+                println!("Part {n}: {:#?}", answer?);
+                self.report_time(&format!("Part {n}"), elapsed);
+                if let Ok(Some(Ok(()))) = verdict {
+                    println!("Part {n} matches the recorded answer");
+                }
+            }
+            OutputFormat::Json => {
+                let mut record = JsonRecord::new(day, Some(n), elapsed);
+                match &answer {
+                    Ok(answer) => record.answer = Some(format!("{answer:?}")),
+                    Err(err) => record.error = Some(format!("{err:#}")),
+                }
+                if let Ok(Some(verdict)) = &verdict {
+                    record.correct = Some(verdict.is_ok());
+                }
+                record.print();
+                answer?;
+            }
+        }
+        if self.check && matches!(verdict, Ok(None)) {
+            eprintln!("No recorded answer for part {n}");
+        }
+        verdict?.transpose()?;
+        Ok(())
+    }
+}
+
+/// Runs `f` and measures how long it took
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -45,6 +266,24 @@ pub enum SolutionPart {
     Both,
 }
 
+impl SolutionPart {
+    /// Determines if running `self` covers `part`
+    #[must_use]
+    pub fn includes(self, part: Self) -> bool {
+        self == Self::Both || self == part
+    }
+
+    /// Number of the part, if it is a single part
+    #[must_use]
+    pub const fn number(self) -> Option<u8> {
+        match self {
+            Self::PartOne => Some(1),
+            Self::PartTwo => Some(2),
+            Self::Both => None,
+        }
+    }
+}
+
 impl ValueEnum for SolutionPart {
     fn value_variants<'a>() -> &'a [Self] {
         &[Self::PartOne, Self::PartTwo, Self::Both]
@@ -72,21 +311,28 @@ impl ValueEnum for SolutionPart {
     }
 }
 
+/// Generates the `main` function for a day's binary
+///
+/// Takes either a type implementing [`Solution`] or the parse and part
+/// functions directly.
 #[macro_export]
 macro_rules! aoc_main {
     ($parse:ident, $part1:ident, $part2:ident) => {
         fn main() -> AocResult<()> {
             let cli = Cli::parse();
+            cli.validate()?;
+            let day = $crate::day_from_package(env!("CARGO_PKG_NAME"));
             let input = cli.input()?;
-            let parsed = $parse(&input);
-            let (_, parsed) = aoc_main!(@finalize, parsed);
+            let (parsed, elapsed) = $crate::timed(|| $parse(&input));
+            let parsed = aoc_main!(@finalize, cli, &input, parsed);
+            let parsed = cli.report_parse(day, SolutionPart::Both, parsed, elapsed)?;
             if cli.should_run(SolutionPart::PartOne) {
-                let part1 = $part1(&parsed)?;
-                println!("Part 1: {:#?}", part1);
+                let (part1, elapsed) = $crate::timed(|| $part1(&parsed));
+                cli.report_part(day, SolutionPart::PartOne, part1, elapsed)?;
             }
             if cli.should_run(SolutionPart::PartTwo) {
-                let part2 = $part2(&parsed)?;
-                println!("Part 2: {:#?}", part2);
+                let (part2, elapsed) = $crate::timed(|| $part2(&parsed));
+                cli.report_part(day, SolutionPart::PartTwo, part2, elapsed)?;
             }
             Ok(())
         }
@@ -95,37 +341,44 @@ macro_rules! aoc_main {
     ($parse1:ident, $parse2:ident, $part1:ident, $part2:ident) => {
         fn main() -> AocResult<()> {
             let cli = Cli::parse();
+            cli.validate()?;
+            let day = $crate::day_from_package(env!("CARGO_PKG_NAME"));
             let input = cli.input()?;
             if cli.should_run(SolutionPart::PartOne) {
-                let parsed = $parse1(&input);
-                let (_, parsed) = aoc_main!(@finalize, parsed);
-                let part1 = $part1(&parsed)?;
-                println!("Part 1: {:#?}", part1);
+                let (parsed, elapsed) = $crate::timed(|| $parse1(&input));
+                let parsed = aoc_main!(@finalize, cli, &input, parsed);
+                let parsed = cli.report_parse(day, SolutionPart::PartOne, parsed, elapsed)?;
+                let (part1, elapsed) = $crate::timed(|| $part1(&parsed));
+                cli.report_part(day, SolutionPart::PartOne, part1, elapsed)?;
             }
             if cli.should_run(SolutionPart::PartTwo) {
-                let parsed = $parse2(&input);
-                let (_, parsed) = aoc_main!(@finalize, parsed);
-                let part2 = $part2(&parsed)?;
-                println!("Part 2: {:#?}", part2);
+                let (parsed, elapsed) = $crate::timed(|| $parse2(&input));
+                let parsed = aoc_main!(@finalize, cli, &input, parsed);
+                let parsed = cli.report_parse(day, SolutionPart::PartTwo, parsed, elapsed)?;
+                let (part2, elapsed) = $crate::timed(|| $part2(&parsed));
+                cli.report_part(day, SolutionPart::PartTwo, part2, elapsed)?;
             }
             Ok(())
         }
     };
 
-    (@finalize, $parsed:expr) => {
-        nom::Finish::finish($parsed).map_err(|nom::error::Error { input, code }| {
-            nom::error::Error {
-                input: input.to_string(),
-                code,
-            }
-        })?
-    }
+    (@finalize, $cli:ident, $input:expr, $parsed:expr) => {
+        $cli.finish($input, $parsed)
+    };
+
+    ($solution:ty) => {
+        fn main() -> $crate::AocResult<()> {
+            let cli = <$crate::Cli as $crate::Parser>::parse()
+                .with_examples(<$solution as $crate::Solution>::EXAMPLES);
+            $crate::run::<$solution>(&cli)
+        }
+    };
 }
 
 #[macro_export]
 macro_rules! assert_part {
     ($parse:ident, $part:ident, $input:expr, $equals:expr) => {{
-        let input = $parse($input).unwrap().1;
+        let input = $crate::finish($input, $parse($input)).unwrap();
         let part = $part(&input).unwrap();
         assert_eq!(format!("{:?}", part), format!("{:?}", $equals))
     }};
@@ -134,7 +387,84 @@ macro_rules! assert_part {
 #[macro_export]
 macro_rules! assert_parser {
     ($parse:ident, $input:expr, $equals:expr) => {{
-        let input = $parse($input).unwrap().1;
+        let input = $crate::finish($input, $parse($input)).unwrap();
         assert_eq!(input, $equals)
     }};
 }
+
+/// Checks that the parsed input renders back into text that parses the same
+#[macro_export]
+macro_rules! assert_round_trip {
+    ($parse:ident, $input:expr) => {{
+        let input = $crate::finish($input, $parse($input)).unwrap();
+        let text = $crate::ToPuzzleText::to_puzzle_text(&input);
+        let again = $crate::finish(&text, $parse(&text)).unwrap();
+        assert_eq!(again, input, "rendered as:\n{}", text)
+    }};
+}
+
+/// Checks a solution against a reference on random inputs
+///
+/// Each input comes from a generator in [`random`], is parsed with
+/// `$parse`, and both functions must give the same answer for it.
+///
+/// ```text
+/// assert_equivalent!(parse, part1, brute_force, |rng| random::springs_report(rng, 12));
+/// ```
+#[macro_export]
+macro_rules! assert_equivalent {
+    ($parse:ident, $fast:ident, $reference:ident, $generate:expr) => {{
+        $crate::random::check_equivalent($generate, |input| {
+            let parsed = $crate::finish(input, $parse(input)).unwrap();
+            (
+                format!("{:?}", $fast(&parsed).unwrap()),
+                format!("{:?}", $reference(&parsed).unwrap()),
+            )
+        })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input() {
+        let cli = Cli::parse_from(["day", "--input-str", "1 2"]);
+        assert_eq!(cli.input().unwrap(), "1 2");
+        let cli = Cli::parse_from(["day", "-e", "2"]).with_examples(&["a", "b"]);
+        assert_eq!(cli.input().unwrap(), "b");
+        let cli = Cli::parse_from(["day", "-e", "3"]).with_examples(&["a", "b"]);
+        assert_eq!(
+            cli.input().unwrap_err().to_string(),
+            "No example 3, there are 2"
+        );
+        assert!(Cli::try_parse_from(["day"]).is_err());
+        assert!(Cli::try_parse_from(["day", "input.txt", "-e", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_answers_path() {
+        let cli = Cli::parse_from(["day", "day-01/input.txt", "--check"]);
+        assert_eq!(
+            cli.answers_path().unwrap(),
+            Path::new("day-01/answers.toml")
+        );
+        let cli = Cli::parse_from(["day", "-e", "1", "--check", "--answers", "a.toml"]);
+        assert_eq!(cli.answers_path().unwrap(), Path::new("a.toml"));
+        for args in [
+            &["day", "-e", "1", "--check"][..],
+            &["day", "--input-str", "1 2", "--check"],
+            &["day", "-", "--check"],
+        ] {
+            let cli = Cli::parse_from(args).with_examples(&["a"]);
+            assert_eq!(
+                cli.validate().unwrap_err().to_string(),
+                "--check needs --answers when the input is not a file"
+            );
+        }
+        // The input itself is still there to read
+        let cli = Cli::parse_from(["day", "-e", "1", "--check"]).with_examples(&["a"]);
+        assert_eq!(cli.input().unwrap(), "a");
+    }
+}
@@ -0,0 +1,60 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::time::Duration;
+
+/// How a day's binary prints its results
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Single line of `--format json` output
+///
+/// `part` is empty when the record describes a parse shared by both parts,
+/// and `answer` is empty whenever `error` is set. `correct` is only filled
+/// in when `--check` found a recorded answer.
+#[derive(Debug, Serialize)]
+pub struct JsonRecord {
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub elapsed_ns: u128,
+    pub error: Option<String>,
+    pub correct: Option<bool>,
+}
+
+impl JsonRecord {
+    #[must_use]
+    pub fn new(day: Option<u8>, part: Option<u8>, elapsed: Duration) -> Self {
+        Self {
+            day,
+            part,
+            answer: None,
+            elapsed_ns: elapsed.as_nanos(),
+            error: None,
+            correct: None,
+        }
+    }
+
+    pub fn print(&self) {
+        match serde_json::to_string(self) {
+            Ok(line) => println!("{line}"),
+            Err(err) => eprintln!("Unable to serialize result: {err}"),
+        }
+    }
+}
+
+/// Extracts the day from a package name like `day-05` or `day2024-05`
+#[must_use]
+pub fn day_from_package(name: &str) -> Option<u8> {
+    let (year, day) = name.strip_prefix("day")?.split_once('-')?;
+    if year.chars().all(|c| c.is_ascii_digit()) {
+        day.parse().ok()
+    } else {
+        None
+    }
+}
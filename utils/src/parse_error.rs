@@ -0,0 +1,203 @@
+use nom::{
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    Offset,
+};
+use std::fmt::Display;
+
+use crate::{AocResult, ParseResult};
+
+/// Where and why a puzzle input failed to parse
+///
+/// Displays as the offending line with a caret under the column, so the
+/// problem can be found without scrolling through the rest of the input.
+///
+/// ```text
+/// line 1, column 5: expected tag (in hand)
+///   |
+/// 1 | 32T3X 765
+///   |     ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the input, counted from 1
+    pub line: usize,
+    /// Character of the line, counted from 1
+    pub column: usize,
+    /// Text of the line, without its line ending
+    pub snippet: String,
+    /// What the innermost parser was looking for
+    pub expected: String,
+    /// Names given with `context`, from the outermost parser in
+    pub context: Vec<String>,
+}
+
+impl ParseError {
+    /// Locates the error of a parse in the whole `input`
+    ///
+    /// The innermost entry of `error` is what went wrong. It is usually a
+    /// character or combinator, but a parser that builds its own error can
+    /// make it a context, which then names what was expected.
+    #[must_use]
+    pub fn new(input: &str, error: &VerboseError<&str>) -> Self {
+        let (rest, expected) = match error.errors.first() {
+            Some((rest, VerboseErrorKind::Char(c))) => (*rest, format!("expected '{c}'")),
+            Some((
+                rest,
+                VerboseErrorKind::Nom(ErrorKind::MapOpt | ErrorKind::MapRes | ErrorKind::Verify),
+            )) => (*rest, String::from("invalid value")),
+            Some((rest, VerboseErrorKind::Nom(kind))) => (
+                *rest,
+                format!("expected {}", kind.description().to_lowercase()),
+            ),
+            Some((rest, VerboseErrorKind::Context(name))) => (*rest, format!("expected {name}")),
+            None => (input, String::from("invalid input")),
+        };
+        let context = error
+            .errors
+            .iter()
+            .skip(1)
+            .rev()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(name) => Some((*name).to_string()),
+                _ => None,
+            })
+            .collect();
+        Self::at(input, input.offset(rest), expected, context)
+    }
+
+    /// Error at byte `offset` of `input`
+    #[must_use]
+    pub fn at(input: &str, offset: usize, expected: String, context: Vec<String>) -> Self {
+        let offset = offset.min(input.len());
+        let before = &input[..offset];
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: input[start..offset].chars().count() + 1,
+            snippet: input[start..end].trim_end_matches('\r').to_string(),
+            expected,
+            context,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.expected
+        )?;
+        if !self.context.is_empty() {
+            write!(f, " (in {})", self.context.join(" > "))?;
+        }
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        writeln!(f)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {}", self.snippet)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Finishes a parse that must consume all of `input`
+///
+/// Only whitespace may be left over. Anything else is reported as an error
+/// at the point where the parser stopped.
+///
+/// # Errors
+///
+/// Returns where in `input` the parse failed or stopped
+pub fn finish<T>(input: &str, parsed: ParseResult<'_, T>) -> AocResult<T> {
+    let (rest, parsed) = locate(input, parsed)?;
+    if rest.trim().is_empty() {
+        Ok(parsed)
+    } else {
+        let offset = input.offset(rest.trim_start());
+        Err(ParseError::at(input, offset, String::from("expected end of input"), vec![]).into())
+    }
+}
+
+/// Finishes a parse of `input`, ignoring whatever the parser left over
+///
+/// # Errors
+///
+/// Returns where in `input` the parse failed
+pub fn finish_lenient<T>(input: &str, parsed: ParseResult<'_, T>) -> AocResult<T> {
+    Ok(locate(input, parsed)?.1)
+}
+
+/// Turns a failed parse into a [`ParseError`]
+fn locate<'a, T>(input: &str, parsed: ParseResult<'a, T>) -> AocResult<(&'a str, T)> {
+    match parsed {
+        Ok(parsed) => Ok(parsed),
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            Err(ParseError::new(input, &error).into())
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            Err(ParseError::at(input, input.len(), String::from("more input"), vec![]).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{line_ending, u32},
+        error::context,
+        multi::count,
+        sequence::delimited,
+    };
+
+    fn parse(input: &str) -> ParseResult<'_, Vec<u32>> {
+        let number = delimited(tag("n "), u32, line_ending);
+        context("numbers", count(context("number", number), 4))(input)
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "n 1\nn 2\nn x3\nn 4\n";
+        let error = finish(input, parse(input)).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.snippet, "n x3");
+        assert_eq!(error.expected, "expected digit");
+        assert_eq!(error.context, ["numbers", "number"]);
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 3: expected digit (in numbers > number)\n  |\n3 | n x3\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let input = "n 1\nn 2\nn 3\nn 4\n \r\n";
+        assert_eq!(finish(input, parse(input)).unwrap(), [1, 2, 3, 4]);
+
+        let input = "n 1\nn 2\nn 3\nn 4\n\n  n 5\n";
+        let error = finish(input, parse(input)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 6, column 3: expected end of input\n  |\n6 |   n 5\n  |   ^"
+        );
+        assert_eq!(finish_lenient(input, parse(input)).unwrap(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_at() {
+        let error = ParseError::at("ab\r\ncd", 5, String::from("end"), vec![]);
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.snippet, "cd");
+        let error = ParseError::at("ab\r\ncd", 2, String::from("end"), vec![]);
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.snippet, "ab");
+    }
+}
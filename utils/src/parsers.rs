@@ -0,0 +1,85 @@
+//! Parsers shared by several days
+
+use nom::error::{VerboseError, VerboseErrorKind};
+
+use crate::ParseResult;
+
+/// Lines of characters that all have the length of the first
+///
+/// Every character goes through `cell`, and one it rejects fails the parse
+/// there, as does a row that is shorter or longer than the first. `name`
+/// says what a cell is in those errors. Parsing stops at the end of the
+/// input or at an empty line.
+pub fn rows<'a, T>(
+    name: &'static str,
+    cell: impl Fn(char) -> Option<T>,
+) -> impl Fn(&'a str) -> ParseResult<'a, Vec<Vec<T>>> {
+    move |input: &'a str| {
+        let fail = |rest: &'a str, expected: &'static str| {
+            Err(nom::Err::Failure(VerboseError {
+                errors: vec![(rest, VerboseErrorKind::Context(expected))],
+            }))
+        };
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        let mut rest = input;
+        loop {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            let line = rest[..end].strip_suffix('\r').unwrap_or(&rest[..end]);
+            if line.is_empty() {
+                break;
+            }
+            let width = rows.first().map_or(usize::MAX, Vec::len);
+            let mut row = Vec::new();
+            for (i, c) in line.char_indices() {
+                if row.len() == width {
+                    return fail(&line[i..], "end of row");
+                }
+                match cell(c) {
+                    Some(value) => row.push(value),
+                    None => return fail(&line[i..], name),
+                }
+            }
+            if row.len() < width && !rows.is_empty() {
+                return fail(&rest[line.len()..], name);
+            }
+            rows.push(row);
+            rest = &rest[line.len()..];
+            rest = rest
+                .strip_prefix("\r\n")
+                .or_else(|| rest.strip_prefix('\n'))
+                .unwrap_or(rest);
+        }
+        if rows.is_empty() {
+            return Err(nom::Err::Error(VerboseError {
+                errors: vec![(input, VerboseErrorKind::Context(name))],
+            }));
+        }
+        Ok((rest, rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finish;
+
+    fn digits(input: &str) -> ParseResult<'_, Vec<Vec<u32>>> {
+        rows("digit", |c| c.to_digit(10))(input)
+    }
+
+    #[test]
+    fn test_rows() {
+        assert_eq!(
+            finish("12\r\n34\n", digits("12\r\n34\n")).unwrap(),
+            [[1, 2], [3, 4]]
+        );
+        let (rest, rows) = digits("12\n34\n\nx").unwrap();
+        assert_eq!((rest, rows.len()), ("\nx", 2));
+
+        let error = |input| finish(input, digits(input)).unwrap_err().to_string();
+        assert!(error("12\n3x\n").starts_with("line 2, column 2: expected digit\n"));
+        assert!(error("12\n3\n").starts_with("line 2, column 2: expected digit\n"));
+        assert!(error("12\n345\n").starts_with("line 2, column 3: expected end of row\n"));
+        assert!(error("").starts_with("line 1, column 1: expected digit\n"));
+    }
+}
@@ -0,0 +1,83 @@
+//! Rendering parsed input back into puzzle text
+//!
+//! Every day's input data can be printed in the format of the puzzle, so
+//! a parser can be checked by parsing its own output again. See
+//! [`assert_round_trip!`](crate::assert_round_trip) for tests and
+//! [`DynSolution::round_trip`](crate::DynSolution::round_trip) for fuzzing.
+
+use std::fmt::Debug;
+
+use crate::{finish, ParseResult};
+
+/// Input data that can be written back in the format of the puzzle
+pub trait ToPuzzleText {
+    /// Renders the text that parses back into `self`
+    ///
+    /// Spacing does not need to match the original input, only the data.
+    fn to_puzzle_text(&self) -> String;
+}
+
+/// Outcome of parsing an input, rendering it and parsing it again
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoundTrip {
+    /// The input did not parse
+    Rejected,
+    /// The rendered text parsed back into the same data
+    Kept,
+    /// The rendered text failed to parse or gave different data
+    Changed { rendered: String },
+}
+
+/// Parses `input`, renders the result and checks that it parses back
+pub fn round_trip<T>(parse: impl Fn(&str) -> ParseResult<'_, T>, input: &str) -> RoundTrip
+where
+    T: ToPuzzleText + PartialEq,
+{
+    let Ok(parsed) = finish(input, parse(input)) else {
+        return RoundTrip::Rejected;
+    };
+    let rendered = parsed.to_puzzle_text();
+    match finish(&rendered, parse(&rendered)) {
+        Ok(again) if again == parsed => RoundTrip::Kept,
+        _ => RoundTrip::Changed { rendered },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Numbers(Vec<u32>);
+
+    impl ToPuzzleText for Numbers {
+        fn to_puzzle_text(&self) -> String {
+            let numbers: Vec<String> = self.0.iter().map(u32::to_string).collect();
+            numbers.join(",")
+        }
+    }
+
+    fn parse(input: &str) -> ParseResult<'_, Numbers> {
+        use nom::{
+            bytes::complete::tag, character::complete::u32, combinator::map, multi::separated_list1,
+        };
+        map(separated_list1(tag(","), u32), Numbers)(input)
+    }
+
+    /// Parser that changes the numbers, so they never round trip
+    fn lossy(input: &str) -> ParseResult<'_, Numbers> {
+        parse(input).map(|(rest, Numbers(n))| (rest, Numbers(vec![n[0] + 1])))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_eq!(round_trip(parse, "1,2,3"), RoundTrip::Kept);
+        assert_eq!(round_trip(parse, "x"), RoundTrip::Rejected);
+        assert_eq!(
+            round_trip(lossy, "1"),
+            RoundTrip::Changed {
+                rendered: String::from("2")
+            }
+        );
+    }
+}
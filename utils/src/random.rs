@@ -0,0 +1,433 @@
+//! Random puzzle inputs for property tests
+//!
+//! Generators return puzzle text, so a day's own parser turns them into its
+//! input data. Use them with [`assert_equivalent!`](crate::assert_equivalent)
+//! to check a fast solution against a brute force one. [`mutate`] edits
+//! real inputs instead, to fuzz the parsers.
+
+use std::{collections::VecDeque, fmt::Write, ops::Range};
+
+/// Seed used when `AOC_SEED` is not set, so failures can be reproduced
+const DEFAULT_SEED: u64 = 0x5EED_2023;
+
+/// Cases run when `AOC_CASES` is not set
+const DEFAULT_CASES: u64 = 64;
+
+/// Small deterministic random number generator (xorshift64*)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        // Mix the seed so nearby seeds give unrelated streams
+        let mut state = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Self((state ^ (state >> 31)).max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Number in `range`, which must not be empty
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        range.start + self.next_u64() % (range.end - range.start).max(1)
+    }
+
+    /// Index below `len`, which must not be zero
+    pub fn index(&mut self, len: usize) -> usize {
+        usize::try_from(self.range(0..len as u64)).unwrap_or_default()
+    }
+
+    /// True with a probability of `numerator / denominator`
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.range(0..denominator) < numerator
+    }
+
+    /// Random element of a non-empty slice
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+}
+
+/// Runs `check` on inputs from `generate` and panics on the first mismatch
+///
+/// `check` returns the answers of the solution under test and of the
+/// reference. The number of cases and the first seed are read from the
+/// `AOC_CASES` and `AOC_SEED` environment variables.
+///
+/// # Panics
+///
+/// When the answers differ for a generated input
+pub fn check_equivalent(
+    generate: impl Fn(&mut Rng) -> String,
+    check: impl Fn(&str) -> (String, String),
+) {
+    let env = |name, default| {
+        std::env::var(name)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    };
+    let seed = env("AOC_SEED", DEFAULT_SEED);
+    for case in 0..env("AOC_CASES", DEFAULT_CASES) {
+        let seed = seed.wrapping_add(case);
+        let input = generate(&mut Rng::new(seed));
+        let (answer, reference) = check(&input);
+        assert!(
+            answer == reference,
+            "answer {answer} differs from reference {reference} (AOC_SEED={seed}) for input:\n{input}"
+        );
+    }
+}
+
+/// Row of spring conditions followed by the sizes of the damaged groups
+///
+/// ```text
+/// ?#.??#?.# 2,3,1
+/// ```
+///
+/// Most groups are read off the row, so it has at least one arrangement.
+/// A quarter of the reports get groups of their own instead, which can be
+/// longer than the row or not fit it at all.
+pub fn springs_report(rng: &mut Rng, max_len: usize) -> String {
+    let len = 1 + rng.index(max_len.max(1));
+    let mut springs: Vec<bool> = (0..len).map(|_| rng.chance(1, 2)).collect();
+    if !springs.contains(&true) {
+        let i = rng.index(len);
+        springs[i] = true;
+    }
+    let groups: Vec<String> = if rng.chance(1, 4) {
+        (0..=rng.index(3))
+            .map(|_| (1 + rng.index(len + 1)).to_string())
+            .collect()
+    } else {
+        springs
+            .split(|damaged| !damaged)
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len().to_string())
+            .collect()
+    };
+    let row: String = springs
+        .iter()
+        .map(|&damaged| match (rng.chance(1, 2), damaged) {
+            (true, _) => '?',
+            (false, true) => '#',
+            (false, false) => '.',
+        })
+        .collect();
+    format!("{row} {}", groups.join(","))
+}
+
+/// Names of the stages of an almanac, in order
+pub const ALMANAC_STAGES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// `X-to-Y map:` table with non overlapping sources below `limit`
+///
+/// ```text
+/// seed-to-soil map:
+/// 50 98 2
+/// 52 50 48
+/// ```
+pub fn lookup_table(rng: &mut Rng, from: &str, to: &str, limit: u64) -> String {
+    let mut table = format!("{from}-to-{to} map:");
+    let mut start = rng.range(0..limit / 4 + 1);
+    for _ in 0..rng.range(1..5) {
+        if start >= limit {
+            break;
+        }
+        let length = rng.range(1..(limit - start).min(limit / 3 + 1) + 1);
+        let destination = rng.range(0..limit);
+        let _ = write!(table, "\n{destination} {start} {length}");
+        start += length + rng.range(0..limit / 8 + 1);
+    }
+    table
+}
+
+/// Whole almanac with seed ranges and a table for every stage
+///
+/// Every value stays below `limit`, so a brute force over the seeds is fast.
+pub fn almanac(rng: &mut Rng, limit: u64) -> String {
+    let limit = limit.max(2);
+    let seeds: Vec<String> = (0..2 * rng.range(1..4))
+        .map(|i| {
+            if i % 2 == 0 {
+                rng.range(0..limit)
+            } else {
+                rng.range(1..limit / 4 + 2)
+            }
+            .to_string()
+        })
+        .collect();
+    let tables: Vec<String> = ALMANAC_STAGES
+        .windows(2)
+        .map(|names| lookup_table(rng, names[0], names[1], limit))
+        .collect();
+    format!("seeds: {}\n\n{}\n", seeds.join(" "), tables.join("\n\n"))
+}
+
+/// Grid of pipes with a single loop through `S`, and the known answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeLoop {
+    pub input: String,
+    /// Number of tiles on the loop
+    pub length: usize,
+    /// Number of tiles enclosed by the loop
+    pub enclosed: usize,
+}
+
+/// Random loop of pipes in a grid of `rows` by `cols` tiles
+///
+/// The loop is the outline of a random shape grown one block at a time.
+/// Tiles off the loop are filled with random pipes that lead nowhere, and
+/// only the two ends of the loop connect to `S`.
+///
+/// # Panics
+///
+/// When the grid is smaller than 2 by 2
+pub fn pipe_loop(rng: &mut Rng, rows: usize, cols: usize) -> PipeLoop {
+    assert!(rows >= 2 && cols >= 2, "a loop needs at least 2x2 tiles");
+    // Blocks sit between the tiles, so the outline of the shape runs
+    // through tile centres
+    let mut shape = vec![vec![false; cols - 1]; rows - 1];
+    shape[rng.index(rows - 1)][rng.index(cols - 1)] = true;
+    let mut area = 1;
+    for _ in 0..rows * cols * 2 {
+        let (r, c) = (rng.index(rows - 1), rng.index(cols - 1));
+        let touches = [(r, c + 1), (r + 2, c + 1), (r + 1, c), (r + 1, c + 2)]
+            .into_iter()
+            .any(|(r, c)| filled(&shape, r, c));
+        if shape[r][c] || !touches {
+            continue;
+        }
+        shape[r][c] = true;
+        if is_simple(&shape) {
+            area += 1;
+        } else {
+            shape[r][c] = false;
+        }
+    }
+
+    // Tile (r, c) is the corner shared by the blocks around it
+    let mut grid = vec![vec!['.'; cols]; rows];
+    let mut tiles = vec![];
+    for (r, row) in grid.iter_mut().enumerate() {
+        for (c, tile) in row.iter_mut().enumerate() {
+            let nw = filled(&shape, r, c);
+            let ne = filled(&shape, r, c + 1);
+            let sw = filled(&shape, r + 1, c);
+            let se = filled(&shape, r + 1, c + 1);
+            *tile = match (nw != ne, ne != se, sw != se, nw != sw) {
+                (true, false, true, false) => '|',
+                (false, true, false, true) => '-',
+                (true, true, false, false) => 'L',
+                (true, false, false, true) => 'J',
+                (false, false, true, true) => '7',
+                (false, true, true, false) => 'F',
+                _ => *rng.choose(&['.', '.', '.', '|', '-', 'L', 'J', '7', 'F']),
+            };
+            if !(nw == ne && ne == se && se == sw) {
+                tiles.push((r, c));
+            }
+        }
+    }
+    let (r, c) = *rng.choose(&tiles);
+    grid[r][c] = 'S';
+    for (nr, nc) in [
+        (r + 1, c),
+        (r, c + 1),
+        (r.wrapping_sub(1), c),
+        (r, c.wrapping_sub(1)),
+    ] {
+        if nr < rows && nc < cols && !tiles.contains(&(nr, nc)) {
+            grid[nr][nc] = '.';
+        }
+    }
+
+    // Pick's theorem: area = enclosed + length / 2 - 1
+    let length = tiles.len();
+    let enclosed = area + 1 - length / 2;
+    let input = grid
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>() + "\n")
+        .collect();
+    PipeLoop {
+        input,
+        length,
+        enclosed,
+    }
+}
+
+/// Small random edits of a puzzle input, for fuzzing its parser
+///
+/// Characters are replaced, inserted or removed, and whole lines are
+/// repeated or dropped. New characters come from the input itself, so the
+/// result stays close to the puzzle format.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    let mut alphabet: Vec<char> = chars.clone();
+    alphabet.extend(['\n', ' ', '0', '9']);
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    for _ in 0..rng.range(1..5) {
+        let at = rng.index(chars.len() + 1);
+        match rng.range(0..6) {
+            0 if at < chars.len() => chars[at] = *rng.choose(&alphabet),
+            1 => chars.insert(at, *rng.choose(&alphabet)),
+            2 if at < chars.len() => {
+                let end = (at + 1 + rng.index(8)).min(chars.len());
+                chars.drain(at..end);
+            }
+            3 => chars.truncate(at),
+            kind @ (4 | 5) => {
+                let start = chars[..at]
+                    .iter()
+                    .rposition(|&c| c == '\n')
+                    .map_or(0, |i| i + 1);
+                let end = chars[at..]
+                    .iter()
+                    .position(|&c| c == '\n')
+                    .map_or(chars.len(), |i| at + i + 1);
+                if kind == 4 {
+                    let line: Vec<char> = chars[start..end].to_vec();
+                    chars.splice(end..end, line);
+                } else {
+                    chars.drain(start..end);
+                }
+            }
+            _ => {}
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Block of a shape, with `r` and `c` counted from 1 so edges read as empty
+fn filled(shape: &[Vec<bool>], r: usize, c: usize) -> bool {
+    r.checked_sub(1)
+        .zip(c.checked_sub(1))
+        .and_then(|(r, c)| shape.get(r)?.get(c))
+        .copied()
+        .unwrap_or(false)
+}
+
+/// Whether a shape of blocks has an outline that is a single simple loop
+///
+/// That is the case when it has no holes and no two blocks touch only at
+/// a corner.
+fn is_simple(shape: &[Vec<bool>]) -> bool {
+    let rows = shape.len() + 2;
+    let cols = shape[0].len() + 2;
+    for r in 0..rows - 1 {
+        for c in 0..cols - 1 {
+            let nw = filled(shape, r, c);
+            let se = filled(shape, r + 1, c + 1);
+            if nw == se && nw != filled(shape, r, c + 1) && nw != filled(shape, r + 1, c) {
+                return false;
+            }
+        }
+    }
+    // Every empty block must be reachable from outside the shape
+    let mut seen = vec![vec![false; cols]; rows];
+    let mut queue = VecDeque::from([(0_usize, 0_usize)]);
+    seen[0][0] = true;
+    while let Some((r, c)) = queue.pop_front() {
+        for (nr, nc) in [
+            (r + 1, c),
+            (r, c + 1),
+            (r.wrapping_sub(1), c),
+            (r, c.wrapping_sub(1)),
+        ] {
+            if nr < rows && nc < cols && !seen[nr][nc] && !filled(shape, nr, nc) {
+                seen[nr][nc] = true;
+                queue.push_back((nr, nc));
+            }
+        }
+    }
+    (0..rows).all(|r| (0..cols).all(|c| filled(shape, r, c) || seen[r][c]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert!((0..10).all(|_| a.next_u64() == b.next_u64()));
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+        assert!((0..1000).all(|_| (3..9).contains(&a.range(3..9))));
+    }
+
+    #[test]
+    fn test_springs_report() {
+        let mut rng = Rng::new(12);
+        let mut too_long = 0;
+        for _ in 0..100 {
+            let report = springs_report(&mut rng, 10);
+            let (row, groups) = report.split_once(' ').unwrap();
+            assert!(!row.is_empty() && row.len() <= 10, "{report}");
+            assert!(row.chars().all(|c| "?#.".contains(c)), "{report}");
+            let groups: Vec<usize> = groups.split(',').map(|g| g.parse().unwrap()).collect();
+            assert!(groups.iter().all(|&g| g > 0), "{report}");
+            if groups.iter().sum::<usize>() + groups.len() - 1 > row.len() {
+                too_long += 1;
+            }
+        }
+        assert!(too_long > 0);
+    }
+
+    #[test]
+    fn test_almanac() {
+        let almanac = almanac(&mut Rng::new(5), 100);
+        assert!(almanac.starts_with("seeds: "));
+        let headers: Vec<&str> = almanac.lines().filter(|l| l.ends_with("map:")).collect();
+        assert_eq!(headers.len(), 7);
+        assert_eq!(headers[0], "seed-to-soil map:");
+        assert_eq!(headers[6], "humidity-to-location map:");
+    }
+
+    #[test]
+    fn test_pipe_loop() {
+        let mut rng = Rng::new(10);
+        for _ in 0..50 {
+            let pipes = pipe_loop(&mut rng, 7, 9);
+            let lines: Vec<&str> = pipes.input.lines().collect();
+            assert_eq!(lines.len(), 7);
+            assert!(lines.iter().all(|line| line.len() == 9));
+            assert_eq!(pipes.input.matches('S').count(), 1);
+            assert!(pipes.length >= 4 && pipes.length.is_multiple_of(2));
+        }
+        let square = pipe_loop(&mut Rng::new(0), 2, 2);
+        assert_eq!(square.length, 4);
+        assert_eq!(square.enclosed, 0);
+    }
+
+    #[test]
+    fn test_mutate() {
+        let mut rng = Rng::new(3);
+        let input = "12 34\n56 78\n";
+        let mutants: Vec<String> = (0..100).map(|_| mutate(&mut rng, input)).collect();
+        assert!(mutants.iter().any(|mutant| mutant != input));
+        assert!(mutants
+            .iter()
+            .all(|mutant| mutant.chars().all(|c| "0123456789 \n".contains(c))));
+        for _ in 0..100 {
+            mutate(&mut rng, "");
+        }
+    }
+}
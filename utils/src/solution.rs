@@ -0,0 +1,182 @@
+use std::{collections::BTreeMap, fmt::Debug, marker::PhantomData, time::Duration};
+
+use crate::{
+    finish, round_trip, timed, AocResult, Cli, ParseResult, RoundTrip, SolutionPart, ToPuzzleText,
+};
+
+/// A solution for a single day of Advent of Code
+///
+/// Implementing this trait lets a day be called as a library function,
+/// either directly or through a [`Registry`].
+pub trait Solution {
+    /// Day of the calendar this solves
+    const DAY: u8;
+
+    /// Example inputs from the puzzle description, for `--example`
+    const EXAMPLES: &'static [&'static str] = &[];
+
+    /// Parsed puzzle input shared by both parts
+    type Input: ToPuzzleText + PartialEq;
+
+    /// Answer for part 1
+    type Output1: Debug;
+
+    /// Answer for part 2
+    type Output2: Debug;
+
+    /// Parses the puzzle input
+    ///
+    /// # Errors
+    ///
+    /// Fails when the input does not match the puzzle format
+    fn parse(input: &str) -> ParseResult<'_, Self::Input>;
+
+    /// Solves part 1
+    ///
+    /// # Errors
+    ///
+    /// Fails when the puzzle cannot be solved for the input
+    fn part1(input: &Self::Input) -> AocResult<Self::Output1>;
+
+    /// Solves part 2
+    ///
+    /// # Errors
+    ///
+    /// Fails when the puzzle cannot be solved for the input
+    fn part2(input: &Self::Input) -> AocResult<Self::Output2>;
+}
+
+/// Answer of a single part rendered as text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartAnswer {
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+impl PartAnswer {
+    /// Runs a part, timing how long it takes
+    fn timed<T: Debug>(part: impl FnOnce() -> AocResult<T>) -> AocResult<Self> {
+        let (answer, elapsed) = timed(part);
+        Ok(Self {
+            answer: format!("{:?}", answer?),
+            elapsed,
+        })
+    }
+}
+
+/// Answers of a day along with how long each phase took
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub parse: Duration,
+    pub part1: Option<PartAnswer>,
+    pub part2: Option<PartAnswer>,
+}
+
+/// Object safe view of a [`Solution`] so days can be stored in a [`Registry`]
+pub trait DynSolution: Send + Sync {
+    /// Day of the calendar this solves
+    fn day(&self) -> u8;
+
+    /// Parses the input and runs the requested parts
+    ///
+    /// # Errors
+    ///
+    /// Fails when parsing or either of the parts fails
+    fn run(&self, input: &str, part: SolutionPart) -> AocResult<Answers>;
+
+    /// Parses the input, renders it as puzzle text and parses that again
+    fn round_trip(&self, input: &str) -> RoundTrip;
+}
+
+struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> DynSolution for Registered<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, input: &str, part: SolutionPart) -> AocResult<Answers> {
+        let (parsed, parse) = timed(|| S::parse(input));
+        let parsed = finish(input, parsed)?;
+        let mut answers = Answers {
+            parse,
+            ..Answers::default()
+        };
+        if part.includes(SolutionPart::PartOne) {
+            answers.part1 = Some(PartAnswer::timed(|| S::part1(&parsed))?);
+        }
+        if part.includes(SolutionPart::PartTwo) {
+            answers.part2 = Some(PartAnswer::timed(|| S::part2(&parsed))?);
+        }
+        Ok(answers)
+    }
+
+    fn round_trip(&self, input: &str) -> RoundTrip {
+        round_trip(S::parse, input)
+    }
+}
+
+/// Collection of solutions that can be looked up by day
+#[derive(Default)]
+pub struct Registry {
+    days: BTreeMap<u8, Box<dyn DynSolution>>,
+}
+
+impl Registry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a solution, replacing any solution already registered for its day
+    #[must_use]
+    pub fn register<S: Solution + 'static>(mut self) -> Self {
+        self.days
+            .insert(S::DAY, Box::new(Registered::<S>(PhantomData)));
+        self
+    }
+
+    /// Finds the solution for a day
+    #[must_use]
+    pub fn get(&self, day: u8) -> Option<&dyn DynSolution> {
+        self.days.get(&day).map(AsRef::as_ref)
+    }
+
+    /// Iterates over the registered solutions in calendar order
+    pub fn iter(&self) -> impl Iterator<Item = &dyn DynSolution> {
+        self.days.values().map(AsRef::as_ref)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.days.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+}
+
+/// Runs a solution for the command line arguments
+///
+/// # Errors
+///
+/// Fails when the flags do not go together, the input cannot be read or
+/// parsed, or a part fails
+pub fn run<S: Solution>(cli: &Cli) -> AocResult<()> {
+    cli.validate()?;
+    let day = Some(S::DAY);
+    let input = cli.input()?;
+    let (parsed, elapsed) = timed(|| S::parse(&input));
+    let parsed = cli.report_parse(day, SolutionPart::Both, cli.finish(&input, parsed), elapsed)?;
+    if cli.should_run(SolutionPart::PartOne) {
+        let (part1, elapsed) = timed(|| S::part1(&parsed));
+        cli.report_part(day, SolutionPart::PartOne, part1, elapsed)?;
+    }
+    if cli.should_run(SolutionPart::PartTwo) {
+        let (part2, elapsed) = timed(|| S::part2(&parsed));
+        cli.report_part(day, SolutionPart::PartTwo, part2, elapsed)?;
+    }
+    Ok(())
+}
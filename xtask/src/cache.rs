@@ -0,0 +1,153 @@
+use anyhow::{bail, Context};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Text Advent of Code serves instead of an input when the session is missing
+const LOGGED_OUT: &str = "Puzzle inputs differ by user";
+
+/// Folder for everything downloaded from Advent of Code
+///
+/// This is `<cache>/advent-of-code`, or the `AOC_CACHE_DIR` environment
+/// variable when it is set, so nothing downloaded ends up in the repository.
+pub fn cache_root() -> anyhow::Result<PathBuf> {
+    match std::env::var_os("AOC_CACHE_DIR") {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Ok(dirs::cache_dir()
+            .context("No cache directory")?
+            .join("advent-of-code")),
+    }
+}
+
+/// Downloaded puzzle inputs, kept outside of the repository
+///
+/// Inputs live in `YYYY/day-NN.txt` under the [`cache_root`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputCache {
+    root: PathBuf,
+}
+
+impl InputCache {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self {
+            root: cache_root()?,
+        })
+    }
+
+    /// Where the input for a day is kept
+    pub fn path(&self, year: u16, day: u64) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day-{day:0>2}.txt"))
+    }
+
+    /// Cached input for a day, if it was downloaded before
+    pub fn get(&self, year: u16, day: u64) -> Option<String> {
+        fs::read_to_string(self.path(year, day)).ok()
+    }
+
+    /// Saves a downloaded input, unless it is clearly not an input
+    pub fn store(&self, year: u16, day: u64, input: &str) -> anyhow::Result<()> {
+        validate(input).with_context(|| format!("Refusing to cache input for {year} day {day}"))?;
+        let path = self.path(year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, input)?;
+        Ok(())
+    }
+
+    /// Copies the cached input for a day to `location`
+    ///
+    /// When the input is not cached yet it is downloaded with `download`,
+    /// which is never called when `offline` is set.
+    pub fn install(
+        &self,
+        year: u16,
+        day: u64,
+        location: &Path,
+        offline: bool,
+        download: impl FnOnce() -> anyhow::Result<String>,
+    ) -> anyhow::Result<()> {
+        let input = match self.get(year, day) {
+            Some(input) => input,
+            None if offline => bail!(
+                "No cached input for {year} day {day} in {}",
+                self.root.display()
+            ),
+            None => {
+                let input = download()?;
+                self.store(year, day, &input)?;
+                input
+            }
+        };
+        fs::write(location, input)?;
+        Ok(())
+    }
+}
+
+/// Checks that a download looks like a puzzle input
+fn validate(input: &str) -> anyhow::Result<()> {
+    let start = input.trim_start();
+    if start.is_empty() {
+        bail!("The input is empty");
+    }
+    if input.contains(LOGGED_OUT) {
+        bail!("Advent of Code did not recognize the session key in ~/.adventofcode");
+    }
+    if start.starts_with("<!DOCTYPE") || start.starts_with("<html") {
+        bail!("The input is an HTML page");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(name: &str) -> InputCache {
+        let root = std::env::temp_dir().join(format!("xtask-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        InputCache { root }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("1abc2\npqr3stu8vwx\n").is_ok());
+        assert!(validate("").is_err());
+        assert!(validate("  \n").is_err());
+        assert!(validate(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        )
+        .is_err());
+        assert!(validate("<!DOCTYPE html>\n<html lang=\"en-us\"></html>").is_err());
+    }
+
+    #[test]
+    fn test_install() {
+        let cache = cache("install");
+        let location = cache.root.join("input.txt");
+        assert!(cache
+            .install(2023, 1, &location, true, || unreachable!())
+            .is_err());
+        assert!(cache
+            .install(2023, 1, &location, false, || Ok(String::new()))
+            .is_err());
+        assert_eq!(cache.get(2023, 1), None);
+
+        fs::create_dir_all(&cache.root).unwrap();
+        cache
+            .install(2023, 1, &location, false, || Ok(String::from("1abc2\n")))
+            .unwrap();
+        assert_eq!(fs::read_to_string(&location).unwrap(), "1abc2\n");
+        assert_eq!(cache.get(2023, 1).as_deref(), Some("1abc2\n"));
+
+        fs::remove_file(&location).unwrap();
+        cache
+            .install(2023, 1, &location, true, || unreachable!())
+            .unwrap();
+        assert_eq!(fs::read_to_string(&location).unwrap(), "1abc2\n");
+        fs::remove_dir_all(&cache.root).unwrap();
+    }
+}
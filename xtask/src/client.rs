@@ -0,0 +1,369 @@
+use anyhow::{bail, Context};
+use log::debug;
+use reqwest::{blocking as req, header, StatusCode};
+use std::{
+    fs::read_to_string,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Site used when `AOC_BASE_URL` is not set
+const AOC_URL: &str = "https://adventofcode.com";
+
+/// Identifies these tools to Advent of Code, as its maintainer asks
+const USER_AGENT: &str = concat!(
+    "github.com/aquacash5/advent-of-code-2023 xtask/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// When the last request of this process was sent, shared by all clients
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Base url of Advent of Code
+///
+/// Can be pointed at a local server with the `AOC_BASE_URL` environment
+/// variable.
+pub fn base_url() -> String {
+    std::env::var("AOC_BASE_URL").map_or_else(
+        |_| AOC_URL.to_string(),
+        |url| url.trim_end_matches('/').to_string(),
+    )
+}
+
+/// Reads the session key from the `~/.adventofcode` file
+pub fn aoc_session() -> anyhow::Result<String> {
+    let path = dirs::home_dir()
+        .context("No home directory")?
+        .join(".adventofcode");
+    Ok(read_to_string(&path)
+        .with_context(|| format!("Unable to read session key from {}", path.display()))?
+        .trim()
+        .to_string())
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// When the puzzle for a day is released, midnight EST (05:00 UTC)
+pub fn unlock_time(year: u16, day: u64) -> SystemTime {
+    let days = days_from_civil(i64::from(year), 12, i64::try_from(day).unwrap_or(i64::MAX));
+    let seconds = u64::try_from(days * 86_400 + 5 * 3_600).unwrap_or_default();
+    UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+/// Fails for a day whose puzzle is not released at `now`
+fn ensure_unlocked(year: u16, day: u64, now: SystemTime) -> anyhow::Result<()> {
+    let unlock = unlock_time(year, day);
+    if let Ok(wait) = unlock.duration_since(now) {
+        if !wait.is_zero() {
+            let (hours, minutes) = (wait.as_secs() / 3_600, wait.as_secs() / 60 % 60);
+            bail!("{year} day {day} unlocks in {hours}h {minutes:0>2}m");
+        }
+    }
+    Ok(())
+}
+
+/// Client for everything xtask fetches from or sends to Advent of Code
+///
+/// Every request carries the session cookie and an identifying
+/// `User-Agent`. Requests of the whole process are spaced out, pages are
+/// fetched again with a growing delay when the site answers 429 or 5xx,
+/// and nothing about a day is requested before its puzzle unlocks.
+/// Answers are posted only once, as a repeated post could submit the same
+/// answer twice.
+#[derive(Debug)]
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    client: req::Client,
+    interval: Duration,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl AocClient {
+    /// Client for [`base_url`] with the session key from `~/.adventofcode`
+    ///
+    /// Requests are at least a second apart and page fetches are retried
+    /// 3 times, after 2, 4 and 8 seconds.
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self::with_base_url(&base_url(), &aoc_session()?)?
+            .throttle(Duration::from_secs(1))
+            .backoff(3, Duration::from_secs(2)))
+    }
+
+    /// Client without throttling or retries, for a local server
+    pub fn with_base_url(base_url: &str, session: &str) -> anyhow::Result<Self> {
+        let client = req::Client::builder().user_agent(USER_AGENT).build()?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            client,
+            interval: Duration::ZERO,
+            retries: 0,
+            backoff: Duration::ZERO,
+        })
+    }
+
+    /// Sets the shortest time between two requests
+    #[must_use]
+    pub const fn throttle(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets how often a failed request is retried and the first delay
+    ///
+    /// The delay doubles with every retry.
+    #[must_use]
+    pub const fn backoff(mut self, retries: u32, delay: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = delay;
+        self
+    }
+
+    /// Downloads the input for a day
+    pub fn input(&self, year: u16, day: u64) -> anyhow::Result<String> {
+        ensure_unlocked(year, day, SystemTime::now())?;
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+
+    /// Downloads the puzzle page for a day
+    pub fn puzzle(&self, year: u16, day: u64) -> anyhow::Result<String> {
+        ensure_unlocked(year, day, SystemTime::now())?;
+        self.get(&format!("/{year}/day/{day}"))
+    }
+
+    /// Posts an answer and returns the page Advent of Code replied with
+    ///
+    /// The answer is sent once, without retries.
+    pub fn answer(&self, year: u16, day: u64, part: u8, answer: &str) -> anyhow::Result<String> {
+        ensure_unlocked(year, day, SystemTime::now())?;
+        let form = [("level", part.to_string()), ("answer", answer.to_string())];
+        self.send(0, || {
+            self.request(reqwest::Method::POST, &format!("/{year}/day/{day}/answer"))
+                .form(&form)
+        })
+    }
+
+    /// Fetches a page of the site, `path` starting with `/`
+    pub fn get(&self, path: &str) -> anyhow::Result<String> {
+        self.send(self.retries, || self.request(reqwest::Method::GET, path))
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> req::RequestBuilder {
+        self.client
+            .request(method, format!("{}{path}", self.base_url))
+            .header(header::COOKIE, format!("session={}", self.session))
+    }
+
+    /// Sends a request, retrying it up to `retries` times while the site is busy
+    fn send(
+        &self,
+        retries: u32,
+        build: impl Fn() -> req::RequestBuilder,
+    ) -> anyhow::Result<String> {
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            self.wait_turn();
+            let response = build().send()?;
+            let status = response.status();
+            let busy = status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+            if !busy || attempt >= retries {
+                return Ok(response.error_for_status()?.text()?);
+            }
+            let retry_after = response
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok()?.parse().ok())
+                .map(Duration::from_secs);
+            let pause = retry_after.map_or(delay, |after| after.max(delay));
+            debug!(
+                "{} answered {status}, retrying in {pause:?}",
+                response.url()
+            );
+            thread::sleep(pause);
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+
+    /// Sleeps until the throttle interval since the last request has passed
+    fn wait_turn(&self) {
+        let mut last_request = LAST_REQUEST
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(pause) = last_request.and_then(|last| self.interval.checked_sub(last.elapsed()))
+        {
+            thread::sleep(pause);
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+/// Minimal HTTP server standing in for Advent of Code in tests
+#[cfg(test)]
+pub mod stand_in {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Request received by the [`StandIn`]
+    #[derive(Debug)]
+    pub struct Request {
+        pub head: String,
+        pub body: String,
+    }
+
+    pub struct StandIn {
+        pub base_url: String,
+        server: JoinHandle<Vec<Request>>,
+    }
+
+    impl StandIn {
+        /// Answers one request with each `(status, body)` in turn
+        pub fn start(replies: Vec<(u16, String)>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let server = thread::spawn(move || {
+                replies
+                    .into_iter()
+                    .map(|(status, page)| {
+                        let (mut stream, _) = listener.accept().unwrap();
+                        let mut reader = BufReader::new(stream.try_clone().unwrap());
+                        let mut head = String::new();
+                        let mut length = 0;
+                        loop {
+                            let mut line = String::new();
+                            reader.read_line(&mut line).unwrap();
+                            if let Some(value) =
+                                line.to_lowercase().strip_prefix("content-length:")
+                            {
+                                length = value.trim().parse().unwrap();
+                            }
+                            if line == "\r\n" {
+                                break;
+                            }
+                            head.push_str(&line);
+                        }
+                        let mut body = vec![0; length];
+                        reader.read_exact(&mut body).unwrap();
+                        write!(
+                            stream,
+                            "HTTP/1.1 {status} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+                            page.len()
+                        )
+                        .unwrap();
+                        Request {
+                            head,
+                            body: String::from_utf8(body).unwrap(),
+                        }
+                    })
+                    .collect()
+            });
+            Self { base_url, server }
+        }
+
+        /// Waits for every reply to be sent and returns the requests
+        pub fn requests(self) -> Vec<Request> {
+            self.server.join().unwrap()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stand_in::StandIn, *};
+
+    fn client(stand_in: &StandIn) -> AocClient {
+        AocClient::with_base_url(&stand_in.base_url, "secret")
+            .unwrap()
+            .backoff(2, Duration::from_millis(1))
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        let at = |seconds| UNIX_EPOCH + Duration::from_secs(seconds);
+        // 2023-12-01T05:00:00Z
+        assert_eq!(unlock_time(2023, 1), at(1_701_406_800));
+        // 2015-12-25T05:00:00Z
+        assert_eq!(unlock_time(2015, 25), at(1_451_019_600));
+        assert!(ensure_unlocked(2023, 1, at(1_701_406_800)).is_ok());
+        assert!(ensure_unlocked(2023, 1, at(1_701_406_799)).is_err());
+        assert!(ensure_unlocked(2023, 2, at(1_701_406_800)).is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let stand_in = StandIn::start(vec![(200, String::from("1abc2\n"))]);
+        let input = client(&stand_in).input(2023, 1).unwrap();
+        let requests = stand_in.requests();
+        assert_eq!(input, "1abc2\n");
+        let head = requests[0].head.to_lowercase();
+        assert!(head.starts_with("get /2023/day/1/input "));
+        assert!(head.contains("cookie: session=secret"));
+        assert!(head.contains("user-agent: github.com/aquacash5/advent-of-code-2023"));
+    }
+
+    #[test]
+    fn test_backoff() {
+        let stand_in = StandIn::start(vec![
+            (429, String::new()),
+            (503, String::new()),
+            (200, String::from("ok")),
+        ]);
+        assert_eq!(client(&stand_in).get("/2023/leaderboard").unwrap(), "ok");
+        assert_eq!(stand_in.requests().len(), 3);
+
+        let stand_in = StandIn::start(vec![
+            (500, String::new()),
+            (500, String::new()),
+            (500, String::new()),
+        ]);
+        assert!(client(&stand_in).get("/2023").is_err());
+        assert_eq!(stand_in.requests().len(), 3);
+
+        let stand_in = StandIn::start(vec![(404, String::new())]);
+        assert!(client(&stand_in).get("/2023").is_err());
+        assert_eq!(stand_in.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_answer_not_retried() {
+        let stand_in = StandIn::start(vec![(503, String::new())]);
+        assert!(client(&stand_in).answer(2023, 1, 1, "142").is_err());
+        let requests = stand_in.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].head.starts_with("POST /2023/day/1/answer "));
+        assert_eq!(requests[0].body, "level=1&answer=142");
+    }
+
+    #[test]
+    fn test_locked() {
+        let client = AocClient::with_base_url("http://127.0.0.1:9", "secret").unwrap();
+        let error = client.input(9999, 1).unwrap_err();
+        assert!(error.to_string().contains("unlocks in"), "{error}");
+    }
+
+    #[test]
+    fn test_throttle() {
+        let stand_in = StandIn::start(vec![(200, String::new()), (200, String::new())]);
+        let client = client(&stand_in).throttle(Duration::from_millis(200));
+        let start = Instant::now();
+        client.get("/").unwrap();
+        client.get("/").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        stand_in.requests();
+    }
+}
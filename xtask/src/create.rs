@@ -1,15 +1,18 @@
 use anyhow::Context;
-use cargo_metadata::Metadata;
-use indoc::{formatdoc, indoc};
+use indoc::formatdoc;
 use log::debug;
-use reqwest::blocking as req;
 use std::{
     fs::{self, read_to_string, File, OpenOptions},
     io::{self, Write},
     path::Path,
 };
 
-const AOC_YEAR: &str = "2023";
+use crate::{
+    cache::InputCache,
+    client::{base_url, AocClient},
+    project::{Layout, Project},
+    puzzle::{examples, update_readme, ExampleTests},
+};
 
 /// Only create file if path doesn't exist
 fn create_new<P: AsRef<Path>>(path: P) -> io::Result<File> {
@@ -23,19 +26,30 @@ fn create_new<P: AsRef<Path>>(path: P) -> io::Result<File> {
 /// Generates the files for the new day
 ///
 /// Scaffolds the project files for the new day of Advent of Code.
-/// Then, we try to download the input file using the session key
-/// in the `~/.adventofcode` file.
-pub fn generate_day(day: u64, metadata: &Metadata) -> anyhow::Result<()> {
-    let day_folder = format!("day-{day:0>2}");
-    let location = metadata.workspace_root.as_std_path().join(day_folder);
+/// Then, we try to download the input file and the puzzle description
+/// using the session key in the `~/.adventofcode` file. When the puzzle
+/// description is available, its examples are used for the tests.
+pub fn generate_day(project: &Project, day: u64, offline: bool) -> anyhow::Result<()> {
+    let year = project.year;
+    let package = project.package(day);
+    let crate_name = project.crate_name(day);
+    let root = project.root_from_day();
+    let location = project.day_dir(day);
     debug!("New folder location: {}", location.display());
     fs::create_dir_all(location.join("src"))?;
+    let page = if offline {
+        None
+    } else {
+        fetch_puzzle(year, day)
+            .map_err(|err| println!("Unable to retrieve puzzle description: {err}"))
+            .ok()
+    };
     if let Ok(mut file) = create_new(location.join("Cargo.toml")) {
         println!("Creating Cargo.toml");
         file.write_all(
             formatdoc! { r#"
 [package]
-name = "day-{day:0>2}"
+name = "{package}"
 version = "1.0.0"
 edition = "2021"
 
@@ -44,7 +58,7 @@ edition = "2021"
 [dependencies]
 itertools = "0.12.0"
 nom = "7.1.1"
-utils = {{ path = "../utils", version = "*" }}
+utils = {{ path = "{root}/utils", version = "*" }}
 
 "# }
             .as_bytes(),
@@ -52,87 +66,215 @@ utils = {{ path = "../utils", version = "*" }}
     } else {
         println!("Cargo.toml exists");
     }
-    if let Ok(mut file) = create_new(location.join("src").join("main.rs")) {
-        println!("Creating main.rs");
+    if let Ok(mut file) = create_new(location.join("src").join("lib.rs")) {
+        println!("Creating lib.rs");
+        let ExampleTests {
+            inputs,
+            examples,
+            part1,
+            part2,
+        } = page
+            .as_deref()
+            .map(|page| ExampleTests::new(&examples(page)))
+            .unwrap_or_default();
         file.write_all(
-            indoc! { r#"
+            formatdoc! { r#"
 use itertools::Itertools;
 #[allow(clippy::wildcard_imports)]
 use utils::*;
 
 #[derive(Debug, PartialEq)]
-struct InputData {}
+pub struct InputData {{}}
+
+impl ToPuzzleText for InputData {{
+    fn to_puzzle_text(&self) -> String {{
+        todo!()
+    }}
+}}
 
-fn parse(input: &str) -> ParseResult<InputData> {
+fn parse(input: &str) -> ParseResult<'_, InputData> {{
     todo!()
-}
+}}
 
 #[allow(clippy::unnecessary_wraps)]
-fn part1(input: &InputData) -> AocResult<()> {
+fn part1(input: &InputData) -> AocResult<()> {{
     Ok(())
-}
+}}
 
 #[allow(clippy::unnecessary_wraps)]
-fn part2(input: &InputData) -> AocResult<()> {
+fn part2(input: &InputData) -> AocResult<()> {{
     Ok(())
-}
+}}
+
+{inputs}
+
+pub struct Day{day:0>2};
+
+impl Solution for Day{day:0>2} {{
+    const DAY: u8 = {day};
+    const EXAMPLES: &'static [&'static str] = &[{examples}];
+
+    type Input = InputData;
+    type Output1 = ();
+    type Output2 = ();
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {{
+        parse(input)
+    }}
 
-aoc_main!(parse, part1, part2);
+    fn part1(input: &InputData) -> AocResult<()> {{
+        part1(input)
+    }}
+
+    fn part2(input: &InputData) -> AocResult<()> {{
+        part2(input)
+    }}
+}}
 
 #[cfg(test)]
-mod tests {
+mod tests {{
     use super::*;
 
-    const INPUT: &str = "";
+    #[test]
+    fn test_parser() {{
+        assert_parser!(parse, INPUT, InputData {{}});
+    }}
 
     #[test]
-    fn test_parser() {
-        assert_parser!(parse, INPUT, InputData {});
-    }
+    fn test_round_trip() {{
+        assert_round_trip!(parse, INPUT);
+    }}
 
     #[test]
-    fn test_part1() {
-        // assert_part!(parse, part1, INPUT, ());
-    }
+    fn test_part1() {{
+        {part1}
+    }}
 
     #[test]
-    fn test_part2() {
-        // assert_part!(parse, part2, INPUT, ());
-    }
-}
+    fn test_part2() {{
+        {part2}
+    }}
+}}
 "# }
             .as_bytes(),
         )?;
+    } else {
+        println!("lib.rs exists");
+    }
+    if let Ok(mut file) = create_new(location.join("src").join("main.rs")) {
+        println!("Creating main.rs");
+        file.write_all(
+            formatdoc! { r"
+use utils::aoc_main;
+
+aoc_main!({crate_name}::Day{day:0>2});
+" }
+            .as_bytes(),
+        )?;
     } else {
         println!("main.rs exists");
     }
+    if project.layout == Layout::Flat {
+        register_day(day, project)?;
+    } else {
+        add_member(project)?;
+    }
     if location.join("input.txt").exists() {
         println!("input.txt exists");
     } else {
-        generate_input(day, &location.join("input.txt"))?;
+        generate_input(year, day, &location.join("input.txt"), offline)?;
+    }
+    if let Some(page) = page {
+        update_readme(&location.join("README.md"), &page, &base_url())?;
     }
     Ok(())
 }
 
-pub fn generate_input(day: u64, location: &Path) -> anyhow::Result<()> {
-    println!("Retrieving input.txt");
-    let aoc_session = read_to_string(
-        dirs::home_dir()
-            .context("No home directory")?
-            .join(".adventofcode"),
-    )?
-    .trim()
-    .to_string();
-    let client = req::Client::new();
-    let input_data = client
-        .request(
-            reqwest::Method::GET,
-            format!("https://adventofcode.com/{AOC_YEAR}/day/{day}/input"),
-        )
-        .header(reqwest::header::COOKIE, format!("session={aoc_session}"))
-        .send()?
-        .error_for_status()?
-        .text()?;
-    fs::write(location, input_data)?;
+/// Adds the day to the `calendar` registry
+///
+/// Inserts the dependency into `calendar/Cargo.toml` and registers the
+/// solution in `calendar/src/lib.rs`, unless they are already there.
+fn register_day(day: u64, project: &Project) -> anyhow::Result<()> {
+    let calendar = project.root.join("calendar");
+
+    let manifest_path = calendar.join("Cargo.toml");
+    let manifest = read_to_string(&manifest_path)?;
+    let dependency = format!("day-{day:0>2} = {{ path = \"../day-{day:0>2}\", version = \"*\" }}");
+    if manifest.contains(&format!("day-{day:0>2} =")) {
+        println!("calendar dependency exists");
+    } else {
+        println!("Adding calendar dependency");
+        let line_end = manifest
+            .rfind("\nday-")
+            .and_then(|i| manifest[i + 1..].find('\n').map(|j| i + j + 2))
+            .context("No day dependencies in calendar/Cargo.toml")?;
+        let mut manifest = manifest;
+        manifest.insert_str(line_end, &format!("{dependency}\n"));
+        fs::write(&manifest_path, manifest)?;
+    }
+
+    let registry_path = calendar.join("src").join("lib.rs");
+    let registry = read_to_string(&registry_path)?;
+    let register = format!("        .register::<day_{day:0>2}::Day{day:0>2}>()");
+    if registry.contains(&register) {
+        println!("calendar registration exists");
+    } else {
+        println!("Registering day in calendar");
+        let line_end = registry
+            .rfind(".register::<")
+            .and_then(|i| registry[i..].find('\n').map(|j| i + j + 1))
+            .context("No registrations in calendar/src/lib.rs")?;
+        let mut registry = registry;
+        registry.insert_str(line_end, &format!("{register}\n"));
+        fs::write(&registry_path, registry)?;
+    }
     Ok(())
 }
+
+/// Adds the folders of the year to the workspace members
+///
+/// Days outside the flat layout are not picked up by the `day-*` glob, so
+/// the year's own glob is added to the root `Cargo.toml` the first time.
+fn add_member(project: &Project) -> anyhow::Result<()> {
+    let manifest_path = project.root.join("Cargo.toml");
+    let manifest = read_to_string(&manifest_path)?;
+    let member = format!("\"{}\"", project.member_pattern());
+    if manifest.contains(&member) {
+        println!("workspace member exists");
+    } else {
+        println!("Adding {member} to the workspace members");
+        let index = manifest
+            .find("members = [")
+            .context("No workspace members in Cargo.toml")?
+            + "members = [".len();
+        let mut manifest = manifest;
+        manifest.insert_str(index, &format!("{member}, "));
+        fs::write(&manifest_path, manifest)?;
+    }
+    Ok(())
+}
+
+/// Puts the input for the day in `location`
+///
+/// Inputs come from the [`InputCache`] and are only downloaded when they
+/// are not cached yet and `offline` is not set.
+pub fn generate_input(year: u16, day: u64, location: &Path, offline: bool) -> anyhow::Result<()> {
+    println!("Retrieving input.txt");
+    InputCache::new()?.install(year, day, location, offline, || {
+        AocClient::new()?.input(year, day)
+    })
+}
+
+/// Downloads the puzzle page for the day
+pub fn fetch_puzzle(year: u16, day: u64) -> anyhow::Result<String> {
+    AocClient::new()?.puzzle(year, day)
+}
+
+/// Saves the puzzle description as Markdown
+///
+/// Running this again after part 1 is solved appends part 2.
+pub fn generate_readme(year: u16, day: u64, location: &Path) -> anyhow::Result<()> {
+    println!("Retrieving puzzle description");
+    let page = fetch_puzzle(year, day)?;
+    update_readme(location, &page, &base_url())
+}
@@ -0,0 +1,226 @@
+use anyhow::{bail, Context};
+use console::style;
+use serde::{de::IgnoredAny, Deserialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    fs,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    cache::cache_root,
+    client::{unlock_time, AocClient},
+};
+
+/// How long Advent of Code asks clients to wait before fetching a leaderboard again
+const REFRESH: Duration = Duration::from_secs(15 * 60);
+
+/// Color for days with only the first star
+const SILVER: u8 = 250;
+
+/// Member of a private leaderboard
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    id: u64,
+    name: Option<String>,
+    stars: u32,
+    local_score: u64,
+    last_star_ts: u64,
+    completion_day_level: BTreeMap<u64, BTreeMap<u8, IgnoredAny>>,
+}
+
+impl Member {
+    /// Name shown on the site, which is hidden for anonymous users
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Number of stars earned on a day
+    pub fn stars_on(&self, day: u64) -> usize {
+        self.completion_day_level.get(&day).map_or(0, BTreeMap::len)
+    }
+}
+
+/// JSON of a private leaderboard
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    owner_id: u64,
+    event: String,
+    members: HashMap<String, Member>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(json).context("Invalid leaderboard")
+    }
+
+    /// Members in the order of the local score
+    ///
+    /// Ties go to whoever got their last star first, like on the site.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|m| {
+            let last_star = if m.stars == 0 {
+                u64::MAX
+            } else {
+                m.last_star_ts
+            };
+            (std::cmp::Reverse(m.local_score), last_star, m.id)
+        });
+        members
+    }
+
+    /// Renders the leaderboard as a table of stars per day
+    ///
+    /// Days that have not unlocked at `now` are left blank.
+    pub fn render(&self, now: SystemTime) -> String {
+        let year = self.event.parse().unwrap_or_default();
+        let unlocked: Vec<bool> = (1..=25).map(|day| unlock_time(year, day) <= now).collect();
+        let owner = self
+            .members
+            .get(&self.owner_id.to_string())
+            .map_or_else(|| self.owner_id.to_string(), Member::display_name);
+
+        let mut table = String::new();
+        let _ = writeln!(
+            table,
+            "{}",
+            style(format!("{} private leaderboard of {owner}", self.event))
+                .bold()
+                .white()
+        );
+        let tens: String = (1..=25)
+            .map(|day| match day / 10 {
+                0 => ' ',
+                tens => char::from_digit(tens, 10).unwrap_or(' '),
+            })
+            .collect();
+        let units: String = (1..=25)
+            .map(|day| char::from_digit(day % 10, 10).unwrap_or(' '))
+            .collect();
+        let _ = writeln!(table, "{:11}{}", "", style(tens).dim());
+        let _ = writeln!(table, "{:11}{}", "", style(units).dim());
+        for (rank, member) in self.ranked().into_iter().enumerate() {
+            let stars: String = (1..=25)
+                .zip(&unlocked)
+                .map(|(day, unlocked)| match member.stars_on(day) {
+                    _ if !unlocked => style(" ").to_string(),
+                    0 => style(".").dim().to_string(),
+                    1 => style("*").bold().color256(SILVER).to_string(),
+                    _ => style("*").bold().yellow().to_string(),
+                })
+                .collect();
+            let _ = writeln!(
+                table,
+                "{}{:>6} {stars} {}",
+                style(format!("{:>3})", rank + 1)).dim(),
+                member.local_score,
+                style(member.display_name()).bold()
+            );
+        }
+        table
+    }
+}
+
+/// Cached file if it was written less than `max_age` ago
+fn fresh(path: &Path, max_age: Duration) -> Option<String> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    if modified.elapsed().ok()? < max_age {
+        fs::read_to_string(path).ok()
+    } else {
+        None
+    }
+}
+
+/// Fetches a private leaderboard, at most once every 15 minutes
+///
+/// With `offline` the cached copy is used however old it is.
+pub fn leaderboard(year: u16, id: u64, offline: bool) -> anyhow::Result<Leaderboard> {
+    let path = cache_root()?
+        .join(year.to_string())
+        .join(format!("leaderboard-{id}.json"));
+    let json = if offline {
+        fs::read_to_string(&path).ok()
+    } else {
+        fresh(&path, REFRESH)
+    };
+    let json = match json {
+        Some(json) => json,
+        None if offline => bail!("No cached leaderboard {id} for {year}"),
+        None => {
+            let json =
+                AocClient::new()?.get(&format!("/{year}/leaderboard/private/view/{id}.json"))?;
+            Leaderboard::parse(&json)?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, &json)?;
+            json
+        }
+    };
+    Leaderboard::parse(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../fixtures/leaderboard.json");
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(leaderboard.event, "2023");
+        assert_eq!(leaderboard.members.len(), 5);
+        let ranked: Vec<String> = leaderboard
+            .ranked()
+            .into_iter()
+            .map(Member::display_name)
+            .collect();
+        assert_eq!(
+            ranked,
+            [
+                "Jack Frost",
+                "Holly Berry",
+                "(anonymous user #1003)",
+                "Rudolph",
+                "Tinsel"
+            ]
+        );
+        let holly = &leaderboard.members["1001"];
+        assert_eq!(holly.stars_on(2), 2);
+        assert_eq!(holly.stars_on(3), 1);
+        assert_eq!(holly.stars_on(4), 0);
+        assert!(Leaderboard::parse("{}").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        let table = leaderboard.render(unlock_time(2023, 4));
+        let lines: Vec<String> = console::strip_ansi_codes(&table)
+            .lines()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(lines[0], "2023 private leaderboard of Holly Berry");
+        assert_eq!(lines[1], "                    1111111111222222");
+        assert_eq!(lines[2], "           1234567890123456789012345");
+        assert_eq!(lines[3], "  1)    24 ***.                      Jack Frost");
+        assert_eq!(lines[4], "  2)    21 ***.                      Holly Berry");
+        assert_eq!(lines[7], "  5)     0 ....                      Tinsel");
+    }
+
+    #[test]
+    fn test_fresh() {
+        let path = std::env::temp_dir().join(format!("xtask-fresh-{}.json", std::process::id()));
+        fs::write(&path, "{}").unwrap();
+        assert_eq!(fresh(&path, REFRESH).as_deref(), Some("{}"));
+        assert_eq!(fresh(&path, Duration::ZERO), None);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(fresh(&path, REFRESH), None);
+    }
+}
@@ -1,21 +1,75 @@
+mod cache;
+mod client;
 mod create;
+mod leaderboard;
+mod project;
+mod puzzle;
+mod submit;
 
+use anyhow::bail;
 use cargo_metadata::{Metadata, MetadataCommand};
 use clap::{builder::PossibleValue, Parser, ValueEnum};
 use log::trace;
-use std::fmt::Display;
+use std::{fmt::Display, time::SystemTime};
 use xshell::{cmd, Shell};
 
-use crate::create::{generate_day, generate_input};
+use crate::{
+    create::{generate_day, generate_input},
+    leaderboard::leaderboard,
+    project::{Layout, Project},
+    submit::submit,
+};
 
 /// Tasks to use and maintain this project
 #[derive(Parser, Debug)]
 #[command(author, about, long_about = None)]
-enum Cli {
+struct Cli {
+    /// Year of Advent of Code, when not set in `[workspace.metadata.aoc]`
+    #[arg(short, long, global = true, env = "AOC_YEAR")]
+    year: Option<u16>,
+
+    /// Where the days of the year live, when not set in `[workspace.metadata.aoc]`
+    #[arg(short, long, global = true, value_enum, env = "AOC_LAYOUT")]
+    layout: Option<Layout>,
+
+    /// Only use inputs from the cache and never download anything
+    #[arg(long, global = true)]
+    offline: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Time the solutions and compare them with the saved baseline
+    ///
+    /// The baseline is kept in target/bench-baseline.json.
+    Bench {
+        /// Only time this day
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// How many times each day is run
+        #[arg(short, long, default_value_t = 10)]
+        iterations: u32,
+
+        /// Save the timings as the new baseline
+        #[arg(short, long)]
+        save: bool,
+
+        /// Slowdown of the median, in percent, reported as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+
     /// Runs clippy on all projects
     Clippy,
 
     /// Creates the scaffolding for the days packages
+    ///
+    /// Running it again for an existing day adds part 2 of the puzzle
+    /// description to the day's README.md once it is unlocked.
     Create {
         /// The day to run
         #[arg(value_parser = clap::value_parser!(u64).range(1..=25))]
@@ -31,6 +85,67 @@ enum Cli {
         /// part of the task to do
         #[arg(short, long, value_enum, default_value_t)]
         part: SolutionPart,
+
+        /// Report how long parsing and each part took
+        #[arg(short, long)]
+        time: bool,
+
+        /// How to print the results
+        #[arg(short, long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+
+        /// Compare the answers with the day's answers.toml and fail on a mismatch
+        #[arg(short, long)]
+        check: bool,
+
+        /// Ignore input the parser left over instead of failing
+        #[arg(long)]
+        lenient: bool,
+
+        /// Run on the N-th example of the puzzle description instead of the input
+        #[arg(short, long, value_name = "N", conflicts_with = "check")]
+        example: Option<usize>,
+    },
+
+    /// Feed mutated inputs to a day's parser until one fails to round trip
+    ///
+    /// The day's input.txt is mutated, and an input that makes the parser
+    /// panic or that does not parse back the same after being rendered is
+    /// saved in target/fuzz.
+    Fuzz {
+        /// The day to fuzz
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// How many mutated inputs to try
+        #[arg(short, long, default_value_t = 10_000)]
+        runs: u64,
+
+        /// Seed of the mutations, to reproduce a finding
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+
+    /// Show a private leaderboard
+    ///
+    /// The leaderboard is fetched at most once every 15 minutes.
+    Leaderboard {
+        /// Id of the leaderboard, the number at the end of its url
+        id: u64,
+    },
+
+    /// Run every day in a single process and print a table of the results
+    RunAll,
+
+    /// Solve a part and submit the answer to Advent of Code
+    Submit {
+        /// The day to submit
+        #[arg(value_parser = clap::value_parser!(u64).range(1..=25))]
+        day: u64,
+
+        /// The part to submit
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
     },
 
     /// Print out a lovely christmas tree
@@ -100,62 +215,162 @@ fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     trace!("CLI arguments: {cli:?}");
     let metadata = MetadataCommand::new().no_deps().exec()?;
+    let project = Project::new(&metadata, cli.year, cli.layout)?;
+    trace!("Project: {project:?}");
 
     let sh = Shell::new()?;
-    match cli {
-        Cli::Clippy => {
+    match cli.command {
+        Command::Bench {
+            day,
+            iterations,
+            save,
+            threshold,
+        } => {
+            download_inputs(&project, &metadata, day.map(u64::from), cli.offline)?;
+            let workspace = &project.root;
+            let baseline = metadata.target_directory.join("bench-baseline.json");
+            let day: Vec<String> = day
+                .map(|day| vec![String::from("--day"), day.to_string()])
+                .unwrap_or_default();
+            let iterations = iterations.to_string();
+            let threshold = format!("--threshold={threshold}");
+            let save = save.then_some("--save");
+            sh.set_var("RUSTFLAGS", "-Awarnings");
+            cmd!(
+                sh,
+                "cargo run -q --release --package calendar -- bench --workspace {workspace} --baseline {baseline} --iterations {iterations} {threshold} {day...} {save...}"
+            )
+            .run()?;
+        }
+        Command::Clippy => {
             cmd!(
                 sh,
                 "cargo clippy -q -- -W clippy::all -W clippy::pedantic -W clippy::nursery"
             )
             .run()?;
         }
-        Cli::Create { day } => {
-            generate_day(day, &metadata)?;
-        }
-        Cli::Day { day, part } => {
-            let package = format!("day-{day:0>2}");
-            let path = metadata
-                .workspace_root
-                .as_std_path()
-                .join(&package)
-                .join("input.txt");
-            if !path.exists() {
-                generate_input(day, &path)?;
-            }
+        Command::Create { day } => {
+            generate_day(&project, day, cli.offline)?;
+        }
+        Command::Day {
+            day,
+            part,
+            time,
+            format,
+            check,
+            lenient,
+            example,
+        } => {
+            let package = project.package(day);
+            let input: Vec<String> = if let Some(example) = example {
+                vec![String::from("--example"), example.to_string()]
+            } else {
+                let path = project.day_dir(day).join("input.txt");
+                if !path.exists() {
+                    generate_input(project.year, day, &path, cli.offline)?;
+                }
+                vec![path.display().to_string()]
+            };
             let part = format!("{part}");
+            let time = time.then_some("--time");
+            let check = check.then_some("--check");
+            let lenient = lenient.then_some("--lenient");
+            sh.set_var("RUSTFLAGS", "-Awarnings");
+            cmd!(
+                sh,
+                "cargo run -q --release --package {package} -- {input...} -p {part} -f {format} {time...} {check...} {lenient...}"
+            )
+            .run()?;
+        }
+        Command::Fuzz { day, runs, seed } => {
+            download_inputs(&project, &metadata, Some(u64::from(day)), cli.offline)?;
+            let workspace = &project.root;
+            let artifacts = metadata.target_directory.join("fuzz");
+            let (day, runs, seed) = (day.to_string(), runs.to_string(), seed.to_string());
             sh.set_var("RUSTFLAGS", "-Awarnings");
             cmd!(
                 sh,
-                "cargo run -q --release --package {package} -- {path} -p {part}"
+                "cargo run -q --release --package calendar -- fuzz --workspace {workspace} --artifacts {artifacts} --day {day} --runs {runs} --seed {seed}"
             )
             .run()?;
         }
-        Cli::Tree => {
+        Command::Leaderboard { id } => {
+            let leaderboard = leaderboard(project.year, id, cli.offline)?;
+            print!("{}", leaderboard.render(SystemTime::now()));
+        }
+        Command::RunAll => {
+            run_all(&sh, &project, &metadata, cli.offline)?;
+        }
+        Command::Submit { day, part } => {
+            if cli.offline {
+                bail!("Submitting an answer needs the network");
+            }
+            submit(&sh, &project, day, part)?;
+        }
+        Command::Tree => {
             cmd!(sh, "cargo run -q --release --package tree").run()?;
         }
-        Cli::Test { day } => {
-            let day = format!("day-{day:0>2}");
-            cmd!(sh, "cargo test -q --package {day}").run()?;
+        Command::Test { day } => {
+            let package = project.package(day);
+            cmd!(sh, "cargo test -q --package {package}").run()?;
         }
-        Cli::TestAll => {
-            test_all(&sh, &metadata)?;
+        Command::TestAll => {
+            test_all(&sh, &project, &metadata)?;
         }
     }
 
     Ok(())
 }
 
-/// Tests all of the Advent of Code projects in the workspace
-fn test_all(sh: &Shell, metadata: &Metadata) -> anyhow::Result<()> {
-    metadata
-        .workspace_packages()
-        .iter()
-        .filter(|p| p.name.starts_with("day"))
-        .try_for_each(|p| {
-            let name = p.name.clone();
-            cmd!(sh, "cargo test -q -p {name}")
-                .run()
-                .map_err(anyhow::Error::from)
-        })
+/// Downloads the inputs the calendar runner needs, for every day or just one
+///
+/// The calendar only knows the days of the flat layout.
+fn download_inputs(
+    project: &Project,
+    metadata: &Metadata,
+    only: Option<u64>,
+    offline: bool,
+) -> anyhow::Result<()> {
+    if project.layout != Layout::Flat {
+        bail!("The calendar only supports the flat layout");
+    }
+    for (day, _) in project
+        .days(metadata)
+        .filter(|(day, _)| only.is_none_or(|only| *day == only))
+    {
+        let path = project.day_dir(day).join("input.txt");
+        if !path.exists() {
+            generate_input(project.year, day, &path, offline)?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs all of the Advent of Code projects through the calendar runner
+///
+/// Missing inputs are downloaded first so every day has something to run.
+fn run_all(
+    sh: &Shell,
+    project: &Project,
+    metadata: &Metadata,
+    offline: bool,
+) -> anyhow::Result<()> {
+    download_inputs(project, metadata, None, offline)?;
+    let workspace = &project.root;
+    sh.set_var("RUSTFLAGS", "-Awarnings");
+    cmd!(
+        sh,
+        "cargo run -q --release --package calendar -- run-all --workspace {workspace}"
+    )
+    .run()?;
+    Ok(())
+}
+
+/// Tests all of the Advent of Code projects of the year in the workspace
+fn test_all(sh: &Shell, project: &Project, metadata: &Metadata) -> anyhow::Result<()> {
+    project.days(metadata).try_for_each(|(_, name)| {
+        cmd!(sh, "cargo test -q -p {name}")
+            .run()
+            .map_err(anyhow::Error::from)
+    })
 }
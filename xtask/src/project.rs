@@ -0,0 +1,164 @@
+use anyhow::Context;
+use cargo_metadata::Metadata;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Year used when neither the command line nor the workspace sets one
+const DEFAULT_YEAR: u16 = 2023;
+
+/// Where the packages for the days of a year live in the workspace
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Layout {
+    /// `day-NN` folders holding `day-NN` packages, for a single year
+    #[default]
+    Flat,
+    /// `YYYY/day-NN` folders holding `dayYYYY-NN` packages
+    YearDirectory,
+    /// `dayYYYY-NN` folders holding `dayYYYY-NN` packages
+    YearPackage,
+}
+
+/// `[workspace.metadata.aoc]` table of the workspace `Cargo.toml`
+#[derive(Debug, Default, Deserialize)]
+struct AocMetadata {
+    year: Option<u16>,
+    layout: Option<Layout>,
+}
+
+/// The year of Advent of Code being worked on and how its days are laid out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    pub year: u16,
+    pub layout: Layout,
+    pub root: PathBuf,
+}
+
+impl Project {
+    /// Settles the year and layout
+    ///
+    /// Values given on the command line (or through the environment) win
+    /// over the `[workspace.metadata.aoc]` table of the workspace.
+    pub fn new(
+        metadata: &Metadata,
+        year: Option<u16>,
+        layout: Option<Layout>,
+    ) -> anyhow::Result<Self> {
+        let config: AocMetadata = match metadata.workspace_metadata.get("aoc") {
+            Some(aoc) => serde_json::from_value(aoc.clone())
+                .context("Invalid [workspace.metadata.aoc] in Cargo.toml")?,
+            None => AocMetadata::default(),
+        };
+        Ok(Self {
+            year: year.or(config.year).unwrap_or(DEFAULT_YEAR),
+            layout: layout.or(config.layout).unwrap_or_default(),
+            root: metadata.workspace_root.clone().into_std_path_buf(),
+        })
+    }
+
+    /// Name of the package for a day
+    pub fn package(&self, day: u64) -> String {
+        match self.layout {
+            Layout::Flat => format!("day-{day:0>2}"),
+            Layout::YearDirectory | Layout::YearPackage => format!("day{}-{day:0>2}", self.year),
+        }
+    }
+
+    /// Name of the library crate for a day, as used in `use` paths
+    pub fn crate_name(&self, day: u64) -> String {
+        self.package(day).replace('-', "_")
+    }
+
+    /// Folder holding the package for a day
+    pub fn day_dir(&self, day: u64) -> PathBuf {
+        match self.layout {
+            Layout::Flat => self.root.join(format!("day-{day:0>2}")),
+            Layout::YearDirectory => self
+                .root
+                .join(self.year.to_string())
+                .join(format!("day-{day:0>2}")),
+            Layout::YearPackage => self.root.join(self.package(day)),
+        }
+    }
+
+    /// Path from a day's folder back to the workspace root
+    pub const fn root_from_day(&self) -> &'static str {
+        match self.layout {
+            Layout::Flat | Layout::YearPackage => "..",
+            Layout::YearDirectory => "../..",
+        }
+    }
+
+    /// Glob for the workspace `members` that covers the days of the year
+    pub fn member_pattern(&self) -> String {
+        match self.layout {
+            Layout::Flat => String::from("day-*"),
+            Layout::YearDirectory => format!("{}/day-*", self.year),
+            Layout::YearPackage => format!("day{}-*", self.year),
+        }
+    }
+
+    /// Day of a package if it belongs to the year
+    pub fn day_of(&self, package: &str) -> Option<u64> {
+        let prefix = match self.layout {
+            Layout::Flat => String::from("day-"),
+            Layout::YearDirectory | Layout::YearPackage => format!("day{}-", self.year),
+        };
+        package.strip_prefix(&prefix)?.parse().ok()
+    }
+
+    /// Packages of the workspace that belong to the year, with their day
+    pub fn days<'a>(&'a self, metadata: &'a Metadata) -> impl Iterator<Item = (u64, String)> + 'a {
+        metadata
+            .workspace_packages()
+            .into_iter()
+            .filter_map(|p| self.day_of(&p.name).map(|day| (day, p.name.clone())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(layout: Layout) -> Project {
+        Project {
+            year: 2024,
+            layout,
+            root: PathBuf::from("/aoc"),
+        }
+    }
+
+    #[test]
+    fn test_flat() {
+        let project = project(Layout::Flat);
+        assert_eq!(project.package(5), "day-05");
+        assert_eq!(project.crate_name(5), "day_05");
+        assert_eq!(project.day_dir(5), PathBuf::from("/aoc/day-05"));
+        assert_eq!(project.member_pattern(), "day-*");
+        assert_eq!(project.day_of("day-12"), Some(12));
+        assert_eq!(project.day_of("calendar"), None);
+    }
+
+    #[test]
+    fn test_year_directory() {
+        let project = project(Layout::YearDirectory);
+        assert_eq!(project.package(5), "day2024-05");
+        assert_eq!(project.crate_name(5), "day2024_05");
+        assert_eq!(project.day_dir(5), PathBuf::from("/aoc/2024/day-05"));
+        assert_eq!(project.root_from_day(), "../..");
+        assert_eq!(project.member_pattern(), "2024/day-*");
+        assert_eq!(project.day_of("day2024-12"), Some(12));
+        assert_eq!(project.day_of("day2023-12"), None);
+        assert_eq!(project.day_of("day-12"), None);
+    }
+
+    #[test]
+    fn test_year_package() {
+        let project = project(Layout::YearPackage);
+        assert_eq!(project.package(5), "day2024-05");
+        assert_eq!(project.day_dir(5), PathBuf::from("/aoc/day2024-05"));
+        assert_eq!(project.root_from_day(), "..");
+        assert_eq!(project.member_pattern(), "day2024-*");
+    }
+}
@@ -0,0 +1,432 @@
+use anyhow::bail;
+use std::{fs, path::Path};
+
+/// Piece of an html page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    Open { name: &'a str, attributes: &'a str },
+    Close(&'a str),
+    Text(&'a str),
+}
+
+/// Splits html into tags and text
+///
+/// This is only meant for the well formed pages Advent of Code serves, so
+/// comments and doctypes are skipped and nothing is validated.
+pub fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with('<') {
+            let Some(end) = rest.find('>') else {
+                tokens.push(Token::Text(rest));
+                break;
+            };
+            let tag = rest[1..end].trim_end_matches('/');
+            rest = &rest[end + 1..];
+            if let Some(name) = tag.strip_prefix('/') {
+                tokens.push(Token::Close(name.trim()));
+            } else if !tag.starts_with('!') {
+                let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+                tokens.push(Token::Open { name, attributes });
+            }
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+    tokens
+}
+
+/// Finds the value of an attribute in the attributes of a tag
+pub fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = attributes[start..].find('"')?;
+    Some(&attributes[start..start + end])
+}
+
+/// Replaces the html entities used on Advent of Code pages
+pub fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Tokens of every `<article class="day-desc">`, one entry per part
+pub fn articles<'a>(tokens: &[Token<'a>]) -> Vec<Vec<Token<'a>>> {
+    let mut articles = vec![];
+    let mut current: Option<Vec<Token<'a>>> = None;
+    for &token in tokens {
+        match (token, current.as_mut()) {
+            (
+                Token::Open {
+                    name: "article",
+                    attributes,
+                },
+                None,
+            ) if attribute(attributes, "class").is_some_and(|c| c.contains("day-desc")) => {
+                current = Some(vec![]);
+            }
+            (Token::Close("article"), Some(_)) => articles.extend(current.take()),
+            (token, Some(article)) => article.push(token),
+            (_, None) => {}
+        }
+    }
+    articles
+}
+
+/// Converts the tokens of an article into Markdown
+///
+/// Relative links are made absolute with `base_url`.
+pub fn to_markdown(tokens: &[Token<'_>], base_url: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut strong_code = false;
+    let mut links: Vec<String> = vec![];
+    for (i, &token) in tokens.iter().enumerate() {
+        match token {
+            Token::Open { name: "h2", .. } => markdown.push_str("## "),
+            Token::Close("h2" | "p") => markdown.push_str("\n\n"),
+            Token::Close("ul") => markdown.push('\n'),
+            Token::Open { name: "pre", .. } => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            Token::Close("pre") => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            Token::Open { name: "code", .. } if !in_pre => {
+                in_code = true;
+                strong_code = matches!(tokens.get(i + 1), Some(Token::Open { name: "em", .. }));
+                markdown.push_str(if strong_code { "**`" } else { "`" });
+            }
+            Token::Close("code") if !in_pre => {
+                in_code = false;
+                markdown.push_str(if strong_code { "`**" } else { "`" });
+            }
+            Token::Open { name: "em", .. } | Token::Close("em") if !in_pre && !in_code => {
+                markdown.push_str("**");
+            }
+            Token::Open { name: "li", .. } => markdown.push_str("- "),
+            Token::Close("li") => markdown.push('\n'),
+            Token::Open {
+                name: "a",
+                attributes,
+            } => {
+                let href = attribute(attributes, "href").unwrap_or_default();
+                if href.starts_with('/') {
+                    links.push(format!("{base_url}{href}"));
+                } else {
+                    links.push(href.to_string());
+                }
+                markdown.push('[');
+            }
+            Token::Close("a") => {
+                let href = links.pop().unwrap_or_default();
+                markdown.push_str(&format!("]({href})"));
+            }
+            Token::Text(text) if in_pre || !text.trim().is_empty() || !text.contains('\n') => {
+                markdown.push_str(&decode_entities(text));
+            }
+            _ => {}
+        }
+    }
+    markdown.trim_end().to_string() + "\n"
+}
+
+/// Example of a part found on the puzzle page
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// Contents of the first `<pre><code>` block
+    pub input: Option<String>,
+    /// Last emphasized code of the part, which is usually the example answer
+    pub answer: Option<String>,
+}
+
+/// Finds the example input and answer of every part on a puzzle page
+pub fn examples(html: &str) -> Vec<Example> {
+    let tokens = tokenize(html);
+    articles(&tokens)
+        .iter()
+        .map(|article| {
+            let mut example = Example::default();
+            for window in article.windows(4) {
+                match window {
+                    [Token::Open { name: "pre", .. }, Token::Open { name: "code", .. }, Token::Text(text), Token::Close("code")]
+                        if example.input.is_none() =>
+                    {
+                        example.input = Some(decode_entities(text));
+                    }
+                    [Token::Open { name: "code", .. }, Token::Open { name: "em", .. }, Token::Text(text), Token::Close("em")] => {
+                        example.answer = Some(decode_entities(text));
+                    }
+                    _ => {}
+                }
+            }
+            example
+        })
+        .collect()
+}
+
+/// Test code for a new day generated from the puzzle examples
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExampleTests {
+    /// Declarations of the example inputs
+    pub inputs: String,
+    /// Names of the example inputs, for `Solution::EXAMPLES`
+    pub examples: String,
+    /// Body of `test_part1`
+    pub part1: String,
+    /// Body of `test_part2`
+    pub part2: String,
+}
+
+impl Default for ExampleTests {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl ExampleTests {
+    /// Generates the tests, leaving assertions commented out when a part
+    /// has no example
+    pub fn new(examples: &[Example]) -> Self {
+        let part1 = examples.first().cloned().unwrap_or_default();
+        let part2 = examples.get(1).cloned().unwrap_or_default();
+
+        let mut inputs = format!(
+            "const INPUT: &str = {};",
+            string_literal(part1.input.as_deref().unwrap_or_default())
+        );
+        let mut examples = String::from("INPUT");
+        let mut part2_input = "INPUT";
+        if let Some(input) = part2
+            .input
+            .as_deref()
+            .filter(|&i| Some(i) != part1.input.as_deref())
+        {
+            inputs.push_str(&format!(
+                "\nconst INPUT_2: &str = {};",
+                string_literal(input)
+            ));
+            examples.push_str(", INPUT_2");
+            part2_input = "INPUT_2";
+        }
+
+        let assertion = |part: &str, input: &str, answer: Option<&str>| match answer {
+            Some(answer) => format!(
+                "assert_part!(parse, {part}, {input}, {});",
+                answer_literal(answer)
+            ),
+            None => format!("// assert_part!(parse, {part}, {input}, ());"),
+        };
+        Self {
+            inputs,
+            examples,
+            part1: assertion(
+                "part1",
+                "INPUT",
+                part1.answer.as_deref().filter(|_| part1.input.is_some()),
+            ),
+            part2: assertion("part2", part2_input, part2.answer.as_deref()),
+        }
+    }
+}
+
+/// Formats text as a string literal, keeping its newlines
+fn string_literal(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Formats an answer as an integer literal when possible
+fn answer_literal(answer: &str) -> String {
+    if answer.parse::<i128>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}")
+    }
+}
+
+/// Writes the puzzle description to a README, appending parts it is missing
+///
+/// Parts are matched by their heading, so part 2 is added to an existing
+/// README once it unlocks.
+pub fn update_readme(path: &Path, html: &str, base_url: &str) -> anyhow::Result<()> {
+    let tokens = tokenize(html);
+    let sections: Vec<String> = articles(&tokens)
+        .iter()
+        .map(|article| to_markdown(article, base_url))
+        .collect();
+    if sections.is_empty() {
+        bail!("No puzzle description found");
+    }
+    let mut readme = fs::read_to_string(path).unwrap_or_default();
+    let mut changed = false;
+    for section in sections {
+        let heading = section.lines().next().unwrap_or_default();
+        if !readme.lines().any(|line| line == heading) {
+            if !readme.is_empty() {
+                readme.push('\n');
+            }
+            readme.push_str(&section);
+            changed = true;
+        }
+    }
+    if changed {
+        println!("Updating README.md");
+        fs::write(path, readme)?;
+    } else {
+        println!("README.md is up to date");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Page shaped like the 2023 day 1 puzzle, with made-up edge cases
+    const DAY_01: &str = include_str!("../fixtures/synthetic-day-01.html");
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("<p class=\"x\">a &amp; <br/>b</p><!-- hi -->"),
+            vec![
+                Token::Open {
+                    name: "p",
+                    attributes: "class=\"x\""
+                },
+                Token::Text("a &amp; "),
+                Token::Open {
+                    name: "br",
+                    attributes: ""
+                },
+                Token::Text("b"),
+                Token::Close("p")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_articles() {
+        let tokens = tokenize(DAY_01);
+        let articles = articles(&tokens);
+        assert_eq!(articles.len(), 2);
+        assert_eq!(articles[0][1], Token::Text("--- Day 1: Trebuchet?! ---"));
+        assert_eq!(articles[1][1], Token::Text("--- Part Two ---"));
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let tokens = tokenize(DAY_01);
+        let articles = articles(&tokens);
+        let part1 = to_markdown(&articles[0], "https://adventofcode.com");
+        assert!(part1.starts_with("## --- Day 1: Trebuchet?! ---\n\n"));
+        assert!(part1.contains("specific **calibration value** that"));
+        assert!(part1.contains("```\n1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n```\n\n"));
+        assert!(part1
+            .contains("are `12`, `38`, `15`, and `77`. Adding these together produces **`142`**."));
+        assert!(part1.ends_with("**What is the sum of all of the calibration values?**\n"));
+
+        let part2 = to_markdown(&articles[1], "https://adventofcode.com");
+        assert!(part2.contains("`nine` **also** count"));
+        assert!(part2.contains(
+            "- Digits & words both count, see [the about page](https://adventofcode.com/2023/about) for details.\n- Lines <never> contain zero.\n\n"
+        ));
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            examples(DAY_01),
+            vec![
+                Example {
+                    input: Some(String::from("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n")),
+                    answer: Some(String::from("142"))
+                },
+                Example {
+                    input: Some(String::from("two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n")),
+                    answer: Some(String::from("281"))
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_example_tests() {
+        assert_eq!(
+            ExampleTests::new(&examples(DAY_01)),
+            ExampleTests {
+                inputs: String::from("const INPUT: &str = \"1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n\";\nconst INPUT_2: &str = \"two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n\";"),
+                examples: String::from("INPUT, INPUT_2"),
+                part1: String::from("assert_part!(parse, part1, INPUT, 142);"),
+                part2: String::from("assert_part!(parse, part2, INPUT_2, 281);")
+            }
+        );
+
+        let reused = [
+            Example {
+                input: Some(String::from("a \"b\"\\\n")),
+                answer: Some(String::from("12")),
+            },
+            Example {
+                input: None,
+                answer: Some(String::from("abc")),
+            },
+        ];
+        assert_eq!(
+            ExampleTests::new(&reused),
+            ExampleTests {
+                inputs: String::from("const INPUT: &str = \"a \\\"b\\\"\\\\\n\";"),
+                examples: String::from("INPUT"),
+                part1: String::from("assert_part!(parse, part1, INPUT, 12);"),
+                part2: String::from("assert_part!(parse, part2, INPUT, \"abc\");")
+            }
+        );
+
+        assert_eq!(
+            ExampleTests::default(),
+            ExampleTests {
+                inputs: String::from("const INPUT: &str = \"\";"),
+                examples: String::from("INPUT"),
+                part1: String::from("// assert_part!(parse, part1, INPUT, ());"),
+                part2: String::from("// assert_part!(parse, part2, INPUT, ());")
+            }
+        );
+    }
+
+    #[test]
+    fn test_update_readme() {
+        let dir = std::env::temp_dir().join(format!("xtask-readme-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("README.md");
+        let part1_only = &DAY_01[..DAY_01
+            .find("<article class=\"day-desc\"><h2 id=\"part2\">")
+            .unwrap()];
+
+        update_readme(&path, part1_only, "").unwrap();
+        let readme = fs::read_to_string(&path).unwrap();
+        assert!(readme.contains("## --- Day 1: Trebuchet?! ---"));
+        assert!(!readme.contains("## --- Part Two ---"));
+
+        update_readme(&path, DAY_01, "").unwrap();
+        update_readme(&path, DAY_01, "").unwrap();
+        let updated = fs::read_to_string(&path).unwrap();
+        assert!(updated.starts_with(&readme));
+        assert_eq!(updated.matches("## --- Part Two ---").count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
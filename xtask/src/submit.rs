@@ -0,0 +1,348 @@
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+};
+use xshell::{cmd, Shell};
+
+use crate::{
+    client::AocClient,
+    create::{generate_input, generate_readme},
+    project::Project,
+};
+
+/// What Advent of Code said about a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    AlreadySolved,
+    Unknown,
+}
+
+impl Outcome {
+    /// Reads the outcome from the page returned by the answer endpoint
+    pub fn from_reply(html: &str) -> Self {
+        let text = article_text(html);
+        if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("You gave an answer too recently") {
+            Self::RateLimited
+        } else if text.contains("Did you already complete it") {
+            Self::AlreadySolved
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Self::TooHigh
+            } else if text.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Incorrect
+            }
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Outcomes that rule out resubmitting the same answer
+    const fn is_wrong(self) -> bool {
+        matches!(self, Self::Incorrect | Self::TooHigh | Self::TooLow)
+    }
+
+    /// Outcomes worth remembering for the next submission
+    const fn is_final(self) -> bool {
+        matches!(self, Self::Correct) || self.is_wrong()
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect => write!(f, "incorrect"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+            Self::RateLimited => write!(f, "rate limited"),
+            Self::AlreadySolved => write!(f, "already solved"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Text of the `<article>` in a page with the tags removed
+fn article_text(html: &str) -> String {
+    let article = html
+        .find("<article")
+        .and_then(|start| {
+            html[start..]
+                .find("</article>")
+                .map(|end| &html[start..start + end])
+        })
+        .unwrap_or(html);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Submission {
+    part: u8,
+    answer: String,
+    outcome: Outcome,
+}
+
+/// Answers already submitted for a day, kept in `submissions.json`
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct History {
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        if path.exists() {
+            Ok(serde_json::from_str(&fs::read_to_string(path)?)
+                .with_context(|| format!("Invalid submissions in {}", path.display()))?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Explains why an answer should not be submitted, if it shouldn't
+    fn rejection(&self, part: u8, answer: &str) -> Option<String> {
+        let value: Option<i128> = answer.parse().ok();
+        self.submissions
+            .iter()
+            .filter(|s| s.part == part)
+            .find_map(|s| {
+                let previous: Option<i128> = s.answer.parse().ok();
+                match (s.outcome, value, previous) {
+                    (Outcome::Correct, _, _) => {
+                        Some(format!("Part {part} was already solved with {}", s.answer))
+                    }
+                    (outcome, _, _) if outcome.is_wrong() && s.answer == answer => {
+                        Some(format!("{answer} was already submitted and was {outcome}"))
+                    }
+                    (Outcome::TooHigh, Some(v), Some(p)) if v >= p => {
+                        Some(format!("{answer} is not below {p}, which was too high"))
+                    }
+                    (Outcome::TooLow, Some(v), Some(p)) if v <= p => {
+                        Some(format!("{answer} is not above {p}, which was too low"))
+                    }
+                    _ => None,
+                }
+            })
+    }
+}
+
+/// Result line printed by a day binary with `--format json`
+#[derive(Debug, Deserialize)]
+struct JsonAnswer {
+    part: Option<u8>,
+    answer: Option<String>,
+    error: Option<String>,
+}
+
+/// Runs the solution for a part and returns its answer
+fn solve(sh: &Shell, package: &str, input: &Path, part: u8) -> anyhow::Result<String> {
+    let level = part.to_string();
+    sh.set_var("RUSTFLAGS", "-Awarnings");
+    // A failing part exits non-zero, but still reports its error on stdout
+    let output = cmd!(
+        sh,
+        "cargo run -q --release --package {package} -- {input} -p {level} -f json"
+    )
+    .ignore_status()
+    .output()?;
+    let record = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<JsonAnswer>(line).ok())
+        .find(|record| record.part == Some(part));
+    if let Some(error) = record.as_ref().and_then(|record| record.error.as_ref()) {
+        bail!("Solution failed: {error}");
+    }
+    if !output.status.success() {
+        bail!(
+            "Solution failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(record
+        .context("Solution did not report an answer")?
+        .answer
+        .context("Solution did not report an answer")?
+        .trim_matches('"')
+        .to_string())
+}
+
+/// Adds a correct answer to the day's `answers.toml`
+fn record_answer(path: &Path, part: u8, answer: &str) -> anyhow::Result<()> {
+    let key = format!("part{part}");
+    if path.exists() {
+        let answers: toml::Table = fs::read_to_string(path)?
+            .parse()
+            .with_context(|| format!("Invalid answers in {}", path.display()))?;
+        if answers.contains_key(&key) {
+            return Ok(());
+        }
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{key} = {}", toml::Value::String(answer.into()))?;
+    Ok(())
+}
+
+/// Solves a part and submits the answer to Advent of Code
+///
+/// Every submission is recorded in the day's `submissions.json`, so an
+/// answer that was already rejected is never sent again.
+pub fn submit(sh: &Shell, project: &Project, day: u64, part: u8) -> anyhow::Result<()> {
+    let year = project.year;
+    let package = project.package(day);
+    let location = project.day_dir(day);
+    let input = location.join("input.txt");
+    if !input.exists() {
+        generate_input(year, day, &input, false)?;
+    }
+
+    let answer = solve(sh, &package, &input, part)?;
+    println!("Part {part}: {answer}");
+
+    let history_path = location.join("submissions.json");
+    let mut history = History::load(&history_path)?;
+    if let Some(reason) = history.rejection(part, &answer) {
+        bail!("Not submitting: {reason}");
+    }
+
+    let reply = AocClient::new()?.answer(year, day, part, &answer)?;
+    let outcome = Outcome::from_reply(&reply);
+    println!("Answer was {outcome}");
+    if matches!(outcome, Outcome::RateLimited | Outcome::Unknown) {
+        println!("{}", article_text(&reply));
+    }
+    if outcome.is_final() {
+        history.submissions.push(Submission {
+            part,
+            answer: answer.clone(),
+            outcome,
+        });
+        history.save(&history_path)?;
+    }
+    if outcome == Outcome::Correct {
+        record_answer(&location.join("answers.toml"), part, &answer)?;
+        if part == 1 {
+            generate_readme(year, day, &location.join("README.md"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stand_in::StandIn;
+
+    fn reply(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn test_outcome() {
+        let cases = [
+            (
+                "That's the right answer! You are <em>one gold star</em> closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+                Outcome::Incorrect,
+            ),
+            (
+                "That's not the right answer; your answer is too high. Please wait one minute.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low. Please wait one minute.",
+                Outcome::TooLow,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait.",
+                Outcome::RateLimited,
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Outcome::AlreadySolved,
+            ),
+            ("Something else entirely", Outcome::Unknown),
+        ];
+        for (message, outcome) in cases {
+            assert_eq!(Outcome::from_reply(&reply(message)), outcome, "{message}");
+        }
+    }
+
+    #[test]
+    fn test_rejection() {
+        let submission = |part, answer: &str, outcome| Submission {
+            part,
+            answer: answer.to_string(),
+            outcome,
+        };
+        let history = History {
+            submissions: vec![
+                submission(1, "100", Outcome::TooHigh),
+                submission(1, "10", Outcome::TooLow),
+                submission(1, "50", Outcome::Incorrect),
+                submission(2, "7", Outcome::Correct),
+            ],
+        };
+        assert!(history.rejection(1, "50").is_some());
+        assert!(history.rejection(1, "100").is_some());
+        assert!(history.rejection(1, "120").is_some());
+        assert!(history.rejection(1, "5").is_some());
+        assert!(history.rejection(1, "42").is_none());
+        assert!(history.rejection(2, "8").is_some());
+    }
+
+    #[test]
+    fn test_record_answer() {
+        let path = std::env::temp_dir().join(format!("xtask-answers-{}.toml", std::process::id()));
+        fs::write(&path, "# part1 and part2 are filled in by submit\n").unwrap();
+        record_answer(&path, 1, "a \"quoted\" \\ answer").unwrap();
+        record_answer(&path, 1, "ignored").unwrap();
+        record_answer(&path, 2, "281").unwrap();
+        let answers: toml::Table = fs::read_to_string(&path).unwrap().parse().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(answers["part1"].as_str(), Some("a \"quoted\" \\ answer"));
+        assert_eq!(answers["part2"].as_str(), Some("281"));
+    }
+
+    #[test]
+    fn test_post_answer() {
+        let stand_in = StandIn::start(vec![(200, reply("That's the right answer!"))]);
+        let client = AocClient::with_base_url(&stand_in.base_url, "secret").unwrap();
+        let page = client.answer(2022, 5, 2, "46").unwrap();
+        let requests = stand_in.requests();
+        assert_eq!(Outcome::from_reply(&page), Outcome::Correct);
+        assert!(requests[0].head.starts_with("POST /2022/day/5/answer "));
+        assert!(requests[0].head.contains("session=secret"));
+        assert_eq!(requests[0].body, "level=2&answer=46");
+    }
+}